@@ -0,0 +1,146 @@
+//! `GET /venues/{name}/stats`: per-year publication and authorship figures for
+//! one venue, computed from Postgres. Front matter is not counted as a paper.
+//! Results are cached per index generation, so they are recomputed after the
+//! next import and sync rather than on a short TTL.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use redis::AsyncCommands;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::AppState;
+use cscs_core::{cache, papers, venues};
+
+const TOP_CITED: i64 = 10;
+/// Stats keys are generation-scoped, so they can live much longer than search
+/// responses; SEARCH_CACHE_TTL_SECS=0 still disables caching.
+const STATS_CACHE_TTL_SECS: u64 = 24 * 3600;
+
+#[derive(Serialize, sqlx::FromRow)]
+struct YearStats {
+    year: i32,
+    papers: i64,
+    authors: i64,
+    avg_authors_per_paper: f64,
+    /// Authors with no earlier paper at this venue.
+    new_authors: i64,
+    new_author_ratio: f64,
+    median_citations: f64,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+struct Totals {
+    papers: i64,
+    authors: i64,
+    avg_authors_per_paper: f64,
+    median_citations: f64,
+}
+
+/// Papers of the venue that count for statistics.
+const VENUE_PAPERS: &str = "\
+    SELECT p.id, p.year, COALESCE(p.citation_count, 0) AS citations \
+    FROM papers p JOIN venues v ON v.id = p.venue_id \
+    WHERE v.raw_name = $1 AND p.year > 0 AND p.paper_type <> 'front_matter'";
+
+async fn per_year(db: &PgPool, venue: &str) -> Result<Vec<YearStats>, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "WITH vp AS ({VENUE_PAPERS}), \
+              pa AS (SELECT vp.year, pa.author_id FROM vp JOIN paper_authors pa ON pa.paper_id = vp.id), \
+              debut AS (SELECT author_id, min(year) AS year FROM pa GROUP BY author_id), \
+              output AS (SELECT year, count(*) AS papers, \
+                                percentile_cont(0.5) WITHIN GROUP (ORDER BY citations) AS median_citations \
+                         FROM vp GROUP BY year), \
+              authorship AS (SELECT year, count(*) AS authorships, count(DISTINCT author_id) AS authors \
+                          FROM pa GROUP BY year), \
+              newcomers AS (SELECT year, count(*) AS new_authors FROM debut GROUP BY year) \
+         SELECT p.year, p.papers, \
+                COALESCE(a.authors, 0) AS authors, \
+                COALESCE(a.authorships, 0)::float8 / p.papers AS avg_authors_per_paper, \
+                COALESCE(n.new_authors, 0) AS new_authors, \
+                COALESCE(n.new_authors::float8 / NULLIF(a.authors, 0), 0) AS new_author_ratio, \
+                p.median_citations \
+         FROM output p \
+         LEFT JOIN authorship a ON a.year = p.year \
+         LEFT JOIN newcomers n ON n.year = p.year \
+         ORDER BY p.year"
+    ))
+    .bind(venue)
+    .fetch_all(db)
+    .await
+}
+
+async fn totals(db: &PgPool, venue: &str) -> Result<Totals, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "WITH vp AS ({VENUE_PAPERS}) \
+         SELECT count(*) AS papers, \
+                (SELECT count(DISTINCT pa.author_id) FROM vp JOIN paper_authors pa ON pa.paper_id = vp.id) AS authors, \
+                COALESCE((SELECT count(*) FROM vp JOIN paper_authors pa ON pa.paper_id = vp.id)::float8 \
+                         / NULLIF(count(*), 0), 0) AS avg_authors_per_paper, \
+                COALESCE(percentile_cont(0.5) WITHIN GROUP (ORDER BY citations), 0) AS median_citations \
+         FROM vp"
+    ))
+    .bind(venue)
+    .fetch_one(db)
+    .await
+}
+
+async fn compute(db: &PgPool, venue: &str) -> Result<serde_json::Value, sqlx::Error> {
+    let years = per_year(db, venue).await?;
+    let totals = totals(db, venue).await?;
+
+    let top_ids: Vec<i32> = sqlx::query_scalar(&format!(
+        "SELECT id FROM ({VENUE_PAPERS}) vp ORDER BY citations DESC, year DESC, id LIMIT $2"
+    ))
+    .bind(venue)
+    .bind(TOP_CITED)
+    .fetch_all(db)
+    .await?;
+    let top_cited = papers::fetch_by_ids(db, &top_ids).await?;
+
+    Ok(serde_json::json!({
+        "venue": venue,
+        "totals": totals,
+        "years": years,
+        "top_cited": top_cited,
+    }))
+}
+
+/// `GET /venues/{name}/stats`; `name` may be any alias of the venue.
+pub async fn venue_stats(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let venue = venues::canonical_name(&name)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Unknown venue {}", name)))?;
+
+    let mut con = None;
+    let mut cache_key = String::new();
+    if state.cache_ttl > 0
+        && let Ok(mut c) = state.redis.get_multiplexed_async_connection().await
+        && let Ok(generation) = cache::generation(&mut c).await
+    {
+        cache_key = cache::stats_key(generation, venue);
+        con = Some(c);
+    }
+
+    if let Some(ref mut c) = con
+        && let Ok(cached) = c.get::<_, String>(&cache_key).await
+        && let Ok(json) = serde_json::from_str::<serde_json::Value>(&cached)
+    {
+        return Ok(Json(json));
+    }
+
+    let stats = compute(&state.db, venue)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if let Some(ref mut c) = con {
+        let _ = c.set_ex::<_, _, String>(&cache_key, stats.to_string(), STATS_CACHE_TTL_SECS).await;
+    }
+    Ok(Json(stats))
+}
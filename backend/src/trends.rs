@@ -0,0 +1,121 @@
+//! Topic trends. `GET /trends?q=` counts, per year, the papers whose title or
+//! abstract contains a term or phrase, next to the papers published that year
+//! at the same venues, so the share is comparable across years.
+//! `GET /trends/ngrams` serves the newest report of `cscs trends`, which ranks
+//! n-grams by growth.
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::AppState;
+use cscs_core::venues;
+
+const DEFAULT_NGRAMS_LIMIT: usize = 50;
+
+#[derive(Serialize)]
+struct YearTrend {
+    year: i32,
+    matches: i64,
+    papers: i64,
+    /// `matches / papers`.
+    share: f64,
+}
+
+/// `GET /trends?q=<term or phrase>&venue=<name>...`; without `venue`, all
+/// indexed venues. Front matter is not counted.
+pub async fn term_trend(
+    State(state): State<Arc<AppState>>,
+    Query(raw_params): Query<Vec<(String, String)>>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let mut q = String::new();
+    let mut venue_names: Vec<&'static str> = Vec::new();
+    for (key, value) in &raw_params {
+        match key.as_str() {
+            "q" => q = value.trim().to_string(),
+            "venue" => venue_names.push(
+                venues::canonical_name(value)
+                    .ok_or_else(|| (StatusCode::BAD_REQUEST, format!("Unknown venue {}", value)))?,
+            ),
+            _ => {}
+        }
+    }
+    if q.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "q is required".into()));
+    }
+    venue_names.sort();
+    venue_names.dedup();
+    let venue_filter: Option<Vec<&str>> = (!venue_names.is_empty()).then_some(venue_names.clone());
+
+    let papers: Vec<(i32, i64)> = sqlx::query_as(
+        "SELECT p.year, count(*) FROM papers p JOIN venues v ON v.id = p.venue_id \
+         WHERE p.year > 0 AND p.paper_type <> 'front_matter' \
+           AND ($1::text[] IS NULL OR v.raw_name = ANY($1)) \
+         GROUP BY p.year ORDER BY p.year"
+    )
+    .bind(&venue_filter)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let matches: HashMap<i32, i64> = sqlx::query_as::<_, (i32, i64)>(
+        "SELECT p.year, count(*) FROM papers p JOIN venues v ON v.id = p.venue_id \
+         WHERE p.search_doc @@ phraseto_tsquery('english', $2) \
+           AND p.year > 0 AND p.paper_type <> 'front_matter' \
+           AND ($1::text[] IS NULL OR v.raw_name = ANY($1)) \
+         GROUP BY p.year"
+    )
+    .bind(&venue_filter)
+    .bind(&q)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .into_iter()
+    .collect();
+
+    let years: Vec<YearTrend> = papers
+        .into_iter()
+        .map(|(year, papers)| {
+            let matches = matches.get(&year).copied().unwrap_or(0);
+            YearTrend { year, matches, papers, share: matches as f64 / papers as f64 }
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({ "q": q, "venues": venue_names, "years": years })))
+}
+
+#[derive(Deserialize)]
+pub struct NgramsParams {
+    limit: Option<usize>,
+}
+
+/// `GET /trends/ngrams?limit=`: the newest trending n-grams report.
+pub async fn trending_ngrams(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<NgramsParams>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let limit = params.limit.unwrap_or(DEFAULT_NGRAMS_LIMIT).max(1);
+
+    let report: Option<(serde_json::Value,)> = sqlx::query_as(
+        "SELECT row_to_json(r) FROM ( \
+             SELECT computed_at, recent_from, recent_to, baseline_from, baseline_to, \
+                    recent_papers, baseline_papers, ngrams \
+             FROM trend_reports ORDER BY id DESC LIMIT 1) r"
+    )
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let Some((mut report,)) = report else {
+        return Err((StatusCode::NOT_FOUND, "No trends report yet; run `cscs trends`".into()));
+    };
+    if let Some(ngrams) = report.get_mut("ngrams").and_then(|n| n.as_array_mut()) {
+        ngrams.truncate(limit);
+    }
+    Ok(Json(report))
+}
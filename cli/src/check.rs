@@ -0,0 +1,237 @@
+//! `cscs check`: data quality rules over what the parser wrote to Postgres.
+//!
+//!     cscs check [--fail] [--threshold <rule>=<n>]... [--limit <n>] [--json <path>]
+//!
+//! Each rule selects offending papers by `dblp_key`. A rule fails when it
+//! matches more papers than its threshold; rules without a threshold are only
+//! reported. `--fail` exits non-zero on any failed rule, which is also what the
+//! `check` stage of `cscs refresh --check` does.
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+use crate::arg_value;
+use cscs_core::{Config, db};
+
+const DEFAULT_LIMIT: i64 = 20;
+
+struct Rule {
+    name: &'static str,
+    description: &'static str,
+    /// Query returning one `dblp_key` column per offending paper.
+    query: &'static str,
+    /// Offending papers tolerated before the rule fails; `None` = report only.
+    threshold: Option<i64>,
+}
+
+const RULES: &[Rule] = &[
+    Rule {
+        name: "year_missing",
+        description: "year is 0 or missing (unparseable <year>)",
+        query: "SELECT dblp_key FROM papers WHERE year IS NULL OR year = 0",
+        threshold: Some(0),
+    },
+    Rule {
+        name: "empty_title",
+        description: "title is empty",
+        query: "SELECT dblp_key FROM papers WHERE btrim(title) = ''",
+        threshold: Some(0),
+    },
+    Rule {
+        name: "no_authors",
+        // Front matter, keynotes and session entries legitimately have none.
+        description: "full or short paper has no authors",
+        query: "SELECT p.dblp_key FROM papers p \
+                WHERE p.paper_type IN ('full', 'short') \
+                  AND NOT EXISTS (SELECT 1 FROM paper_authors pa WHERE pa.paper_id = p.id)",
+        threshold: Some(0),
+    },
+    Rule {
+        name: "trailing_period",
+        // DBLP ends every title with a period and the parser keeps it, so
+        // this is a count to watch rather than an error.
+        description: "title ends with a period (DBLP's citation style)",
+        query: "SELECT dblp_key FROM papers WHERE title LIKE '%.'",
+        threshold: None,
+    },
+    Rule {
+        name: "duplicate_title",
+        description: "same normalised title twice in one venue and year",
+        query: "SELECT p.dblp_key FROM papers p \
+                JOIN (SELECT venue_id, year, normalize_title(title) AS t FROM papers \
+                      GROUP BY 1, 2, 3 HAVING count(*) > 1) d \
+                  ON d.venue_id = p.venue_id AND d.year IS NOT DISTINCT FROM p.year \
+                 AND d.t = normalize_title(p.title)",
+        threshold: None,
+    },
+];
+
+#[derive(Serialize)]
+pub struct RuleResult {
+    rule: &'static str,
+    description: &'static str,
+    count: i64,
+    threshold: Option<i64>,
+    failed: bool,
+    /// First offending keys, up to `--limit`.
+    dblp_keys: Vec<String>,
+}
+
+/// `--threshold rule=n` overrides, repeatable.
+fn thresholds(args: &[String]) -> Result<HashMap<String, i64>> {
+    let mut overrides = HashMap::new();
+    for (i, arg) in args.iter().enumerate() {
+        if arg != "--threshold" {
+            continue;
+        }
+        let value = args.get(i + 1).context("--threshold needs <rule>=<n>")?;
+        let (rule, n) = value
+            .split_once('=')
+            .with_context(|| format!("Invalid --threshold {}; expected <rule>=<n>", value))?;
+        if !RULES.iter().any(|r| r.name == rule) {
+            bail!("Unknown rule `{}` in --threshold", rule);
+        }
+        let n = n
+            .parse()
+            .with_context(|| format!("Threshold for {} must be a whole number", rule))?;
+        overrides.insert(rule.to_string(), n);
+    }
+    Ok(overrides)
+}
+
+/// Run every rule and print a summary.
+pub async fn run_rules(pool: &PgPool, args: &[String]) -> Result<Vec<RuleResult>> {
+    let overrides = thresholds(args)?;
+    let limit: i64 = arg_value(args, "--limit")
+        .map(|l| l.parse().context("--limit must be a whole number"))
+        .transpose()?
+        .unwrap_or(DEFAULT_LIMIT);
+
+    let mut results = Vec::with_capacity(RULES.len());
+    for rule in RULES {
+        let (count,): (i64,) = sqlx::query_as(&format!("SELECT count(*) FROM ({}) r", rule.query))
+            .fetch_one(pool)
+            .await
+            .with_context(|| format!("Rule {} failed to run", rule.name))?;
+        let dblp_keys: Vec<String> = if count > 0 && limit > 0 {
+            sqlx::query_scalar(&format!("SELECT dblp_key FROM ({}) r ORDER BY dblp_key LIMIT $1", rule.query))
+                .bind(limit)
+                .fetch_all(pool)
+                .await?
+        } else {
+            Vec::new()
+        };
+        let threshold = overrides.get(rule.name).copied().or(rule.threshold);
+
+        results.push(RuleResult {
+            rule: rule.name,
+            description: rule.description,
+            count,
+            threshold,
+            failed: threshold.is_some_and(|t| count > t),
+            dblp_keys,
+        });
+    }
+
+    for r in &results {
+        let verdict = match (r.failed, r.threshold) {
+            (true, _) => "FAIL",
+            (false, Some(_)) => "ok",
+            (false, None) => "info",
+        };
+        println!("{:<5} {:<16} {:>8}  {}", verdict, r.rule, r.count, r.description);
+        for key in &r.dblp_keys {
+            println!("        {}", key);
+        }
+        if r.count > r.dblp_keys.len() as i64 && !r.dblp_keys.is_empty() {
+            println!("        ... and {} more", r.count - r.dblp_keys.len() as i64);
+        }
+    }
+    Ok(results)
+}
+
+/// Error naming the failed rules, if any.
+pub fn enforce(results: &[RuleResult]) -> Result<()> {
+    let failed: Vec<String> = results
+        .iter()
+        .filter(|r| r.failed)
+        .map(|r| format!("{} ({} > {})", r.rule, r.count, r.threshold.unwrap_or(0)))
+        .collect();
+    if !failed.is_empty() {
+        bail!("Data quality thresholds exceeded: {}", failed.join(", "));
+    }
+    Ok(())
+}
+
+pub async fn run(args: &[String]) -> Result<()> {
+    let config = Config::from_env()?;
+    db::check(&config.database_url).await?;
+    let pool = db::connect(&config.database_url, 2).await?;
+
+    let results = run_rules(&pool, args).await?;
+    if let Some(path) = arg_value(args, "--json") {
+        std::fs::write(&path, serde_json::to_string_pretty(&results)?)
+            .with_context(|| format!("Failed to write {}", path))?;
+    }
+    if args.contains(&"--fail".to_string()) {
+        enforce(&results)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    fn result(rule: &'static str, count: i64, threshold: Option<i64>) -> RuleResult {
+        RuleResult {
+            rule,
+            description: "",
+            count,
+            threshold,
+            failed: threshold.is_some_and(|t| count > t),
+            dblp_keys: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn thresholds_parse_repeated_overrides() {
+        let parsed = thresholds(&args(&["check", "--threshold", "no_authors=10", "--threshold", "empty_title=2"])).unwrap();
+        assert_eq!(parsed.get("no_authors"), Some(&10));
+        assert_eq!(parsed.get("empty_title"), Some(&2));
+        assert!(thresholds(&args(&["check"])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn thresholds_reject_bad_input() {
+        assert!(thresholds(&args(&["--threshold"])).is_err());
+        assert!(thresholds(&args(&["--threshold", "no_authors"])).is_err());
+        assert!(thresholds(&args(&["--threshold", "no_authors=many"])).is_err());
+        assert!(thresholds(&args(&["--threshold", "no_such_rule=1"])).is_err());
+    }
+
+    #[test]
+    fn enforce_passes_within_thresholds_and_report_only_rules() {
+        let results = vec![result("no_authors", 3, Some(3)), result("trailing_period", 900, None)];
+        assert!(enforce(&results).is_ok());
+    }
+
+    #[test]
+    fn enforce_names_every_failed_rule() {
+        let results = vec![
+            result("year_missing", 1, Some(0)),
+            result("no_authors", 12, Some(10)),
+            result("empty_title", 0, Some(0)),
+        ];
+        let err = enforce(&results).unwrap_err().to_string();
+        assert!(err.contains("year_missing (1 > 0)"));
+        assert!(err.contains("no_authors (12 > 10)"));
+        assert!(!err.contains("empty_title"));
+    }
+}
@@ -0,0 +1,40 @@
+//! `cscs` — operator commands for a CSCS deployment.
+//!
+//!     cscs refresh [--dump <dblp.xml>] [--resume] [--no-enrich] [--max-age-days <n>] [--check] [--prune-missing] [--report <path>]
+//!     cscs check [--fail] [--threshold <rule>=<n>]... [--limit <n>] [--json <path>]
+//!     cscs trends [--recent <years>] [--baseline <years>] [--min-count <n>] [--top <n>] [--json <path>]
+//!
+//! Run from the repository root: stages run the `parser` and `sync` binaries
+//! built next to this one, inside their crate directories so each picks up
+//! its own `.env`.
+
+use anyhow::{Result, bail};
+use std::env;
+
+mod check;
+mod refresh;
+mod trends;
+
+/// Value following `flag` on the command line, e.g. `--dump ./dblp.xml`.
+fn arg_value(args: &[String], flag: &str) -> Option<String> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("refresh") => refresh::run(&args[1..]).await,
+        Some("check") => check::run(&args[1..]).await,
+        Some("trends") => trends::run(&args[1..]).await,
+        Some(other) => bail!("Unknown command `{}`; expected `refresh`, `check` or `trends`", other),
+        None => bail!(
+            "Usage:\n  cscs refresh [--dump <dblp.xml>] [--resume] [--no-enrich] [--max-age-days <n>] [--check] [--prune-missing] [--report <path>]\n  \
+             cscs check [--fail] [--threshold <rule>=<n>]... [--limit <n>] [--json <path>]\n  \
+             cscs trends [--recent <years>] [--baseline <years>] [--min-count <n>] [--top <n>] [--json <path>]"
+        ),
+    }
+}
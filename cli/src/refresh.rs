@@ -0,0 +1,375 @@
+//! `cscs refresh`: fetch (or take) a DBLP dump, import it, optionally run the
+//! quality checks (`--check`), enrich the new papers, recompute the trending
+//! n-grams report, sync Meilisearch and invalidate the search cache.
+//!
+//! The run report is rewritten after every stage, so it doubles as the
+//! checkpoint: `--resume` continues a failed or interrupted run from its first
+//! unfinished stage. A Postgres advisory lock keeps two refreshes from
+//! overlapping, and is released by Postgres if this process dies.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use sqlx::Connection;
+use sqlx::postgres::PgConnection;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::arg_value;
+use crate::check;
+use crate::trends;
+use cscs_core::{Config, cache, db};
+
+const DUMP_URL: &str = "https://dblp.org/xml/dblp.xml.gz";
+const DEFAULT_REPORT: &str = "refresh-report.json";
+/// Papers enriched more recently than this are not looked up again; the import
+/// queues every paper, so without it each refresh re-enriches the whole corpus.
+/// Override with `--max-age-days` (0 refetches everything).
+const DEFAULT_MAX_AGE_DAYS: u32 = 30;
+/// Key for `pg_try_advisory_lock`; any value works as long as only refreshes use it.
+const LOCK_KEY: i64 = 0x6373_6373;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Stage {
+    Download,
+    Import,
+    Check,
+    Enrich,
+    Trends,
+    Sync,
+    InvalidateCache,
+    Cleanup,
+}
+
+impl Stage {
+    fn name(self) -> &'static str {
+        match self {
+            Stage::Download => "download",
+            Stage::Import => "import",
+            Stage::Check => "check",
+            Stage::Enrich => "enrich",
+            Stage::Trends => "trends",
+            Stage::Sync => "sync",
+            Stage::InvalidateCache => "invalidate_cache",
+            Stage::Cleanup => "cleanup",
+        }
+    }
+}
+
+const STAGES: [Stage; 8] = [
+    Stage::Download,
+    Stage::Import,
+    Stage::Check,
+    Stage::Enrich,
+    Stage::Trends,
+    Stage::Sync,
+    Stage::InvalidateCache,
+    Stage::Cleanup,
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    Pending,
+    Running,
+    Done,
+    Skipped,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct StageReport {
+    stage: Stage,
+    status: Status,
+    /// Seconds since the Unix epoch.
+    started_at: Option<u64>,
+    finished_at: Option<u64>,
+    duration_secs: Option<f64>,
+    error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct RunReport {
+    status: Status,
+    started_at: u64,
+    finished_at: Option<u64>,
+    /// Dump being imported; unset until the download stage has run.
+    dump: Option<PathBuf>,
+    stages: Vec<StageReport>,
+}
+
+impl RunReport {
+    fn new(dump: Option<PathBuf>, check: bool) -> Self {
+        let local = dump.is_some();
+        Self {
+            status: Status::Pending,
+            started_at: now(),
+            finished_at: None,
+            dump,
+            stages: STAGES
+                .iter()
+                .map(|&stage| StageReport {
+                    stage,
+                    // A local dump is neither downloaded nor cleaned up.
+                    status: if (local && matches!(stage, Stage::Download | Stage::Cleanup))
+                        || (!check && stage == Stage::Check)
+                    {
+                        Status::Skipped
+                    } else {
+                        Status::Pending
+                    },
+                    started_at: None,
+                    finished_at: None,
+                    duration_secs: None,
+                    error: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("No run report at {} to resume from", path.display()))?;
+        serde_json::from_str(&raw).with_context(|| format!("Failed to parse run report {}", path.display()))
+    }
+
+    /// Write via a temporary file so a crash never leaves a truncated report.
+    fn save(&self, path: &Path) -> Result<()> {
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, path).with_context(|| format!("Failed to write run report {}", path.display()))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+pub async fn run(args: &[String]) -> Result<()> {
+    let config = Config::from_env()?;
+    let report_path = PathBuf::from(arg_value(args, "--report").unwrap_or_else(|| DEFAULT_REPORT.into()));
+    let resume = args.contains(&"--resume".to_string());
+    let no_enrich = args.contains(&"--no-enrich".to_string());
+    let max_age_days: u32 = arg_value(args, "--max-age-days")
+        .map(|d| d.parse().context("--max-age-days must be a whole number of days"))
+        .transpose()?
+        .unwrap_or(DEFAULT_MAX_AGE_DAYS);
+    let max_age_days = max_age_days.to_string();
+
+    for dir in ["parser", "sync"] {
+        if !Path::new(dir).is_dir() {
+            bail!("`{}/` not found; run cscs from the repository root", dir);
+        }
+    }
+    let bin_dir = std::env::current_exe()?
+        .parent()
+        .context("cscs binary has no parent directory")?
+        .to_path_buf();
+
+    // Held until this connection closes, i.e. for the rest of the run.
+    let mut lock = PgConnection::connect(&config.database_url)
+        .await
+        .context("Failed to connect to Postgres")?;
+    let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+        .bind(LOCK_KEY)
+        .fetch_one(&mut lock)
+        .await?;
+    if !locked {
+        bail!("Another refresh is already running");
+    }
+
+    let mut report = if resume {
+        let report = RunReport::load(&report_path)?;
+        if report.status == Status::Done {
+            println!("Last refresh finished successfully; nothing to resume.");
+            return Ok(());
+        }
+        println!("Resuming refresh started at {}", report.started_at);
+        report
+    } else {
+        let dump = arg_value(args, "--dump")
+            .map(|d| std::path::absolute(&d).with_context(|| format!("Invalid dump path {}", d)))
+            .transpose()?;
+        if let Some(ref d) = dump
+            && !d.is_file()
+        {
+            bail!("Dump {} does not exist", d.display());
+        }
+        RunReport::new(dump, args.contains(&"--check".to_string()))
+    };
+    if no_enrich {
+        for s in report.stages.iter_mut() {
+            if s.stage == Stage::Enrich && s.status != Status::Done {
+                s.status = Status::Skipped;
+            }
+        }
+    }
+    report.status = Status::Running;
+    report.finished_at = None;
+    report.save(&report_path)?;
+
+    let total = report.stages.len();
+    for i in 0..total {
+        let stage = report.stages[i].stage;
+        if matches!(report.stages[i].status, Status::Done | Status::Skipped) {
+            let state = if report.stages[i].status == Status::Done { "already done" } else { "skipped" };
+            println!("[{}/{}] {}: {}", i + 1, total, stage.name(), state);
+            continue;
+        }
+
+        println!("[{}/{}] {}: running", i + 1, total, stage.name());
+        report.stages[i].status = Status::Running;
+        report.stages[i].started_at = Some(now());
+        report.stages[i].error = None;
+        report.save(&report_path)?;
+
+        let started = Instant::now();
+        let result = match stage {
+            Stage::Download => download(Path::new("parser")).await.map(|path| {
+                report.dump = Some(path);
+            }),
+            Stage::Import => match report.dump {
+                Some(ref dump) => {
+                    let dump = dump.to_string_lossy();
+                    let mut import_args = vec!["--enqueue", "--dump", &dump];
+                    if args.contains(&"--prune-missing".to_string()) {
+                        import_args.push("--prune-missing");
+                    }
+                    run_service(&bin_dir, "parser", &import_args).await
+                }
+                None => Err(anyhow::anyhow!("No dump to import; the download stage did not finish")),
+            },
+            Stage::Check => run_checks(&config, args).await,
+            Stage::Enrich => run_service(&bin_dir, "parser", &["--worker", "--max-age-days", &max_age_days]).await,
+            Stage::Trends => run_trends(&config, args).await,
+            Stage::Sync => run_service(&bin_dir, "sync", &[]).await,
+            Stage::InvalidateCache => invalidate_cache(&config).await,
+            Stage::Cleanup => cleanup(report.dump.as_deref()),
+        };
+
+        let s = &mut report.stages[i];
+        s.finished_at = Some(now());
+        s.duration_secs = Some(started.elapsed().as_secs_f64());
+        match result {
+            Ok(()) => {
+                s.status = Status::Done;
+                println!("[{}/{}] {}: done in {:.1}s", i + 1, total, stage.name(), started.elapsed().as_secs_f64());
+            }
+            // The trends report is a nice-to-have; a small local dump or a new
+            // venue can leave a window empty, which must not block publishing.
+            Err(e) if stage == Stage::Trends => {
+                s.status = Status::Skipped;
+                s.error = Some(format!("{:#}", e));
+                eprintln!("[{}/{}] {}: skipped ({:#})", i + 1, total, stage.name(), e);
+            }
+            Err(e) => {
+                s.status = Status::Failed;
+                s.error = Some(format!("{:#}", e));
+                report.status = Status::Failed;
+                report.finished_at = Some(now());
+                report.save(&report_path)?;
+                eprintln!("[{}/{}] {}: failed", i + 1, total, stage.name());
+                return Err(e.context(format!(
+                    "Refresh failed; fix the cause and continue with `cscs refresh --resume` (report: {})",
+                    report_path.display()
+                )));
+            }
+        }
+        report.save(&report_path)?;
+    }
+
+    report.status = Status::Done;
+    report.finished_at = Some(now());
+    report.save(&report_path)?;
+    println!("Refresh completed; report written to {}", report_path.display());
+    Ok(())
+}
+
+/// Download and decompress the current DBLP dump into `dir`.
+async fn download(dir: &Path) -> Result<PathBuf> {
+    let gz_path = dir.join("dblp.xml.gz");
+    let xml_path = dir.join("dblp.xml");
+
+    println!("Downloading {}...", DUMP_URL);
+    let mut resp = reqwest::get(DUMP_URL).await?.error_for_status()?;
+    let mut file = tokio::fs::File::create(&gz_path).await?;
+    let mut bytes: u64 = 0;
+    while let Some(chunk) = resp.chunk().await.context("Dump download interrupted")? {
+        file.write_all(&chunk).await?;
+        bytes += chunk.len() as u64;
+    }
+    file.flush().await?;
+    println!("Downloaded {} MB, extracting...", bytes / 1_000_000);
+
+    let (gz, xml) = (gz_path.clone(), xml_path.clone());
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let mut decoder = flate2::read::GzDecoder::new(std::io::BufReader::new(std::fs::File::open(&gz)?));
+        let mut out = std::io::BufWriter::new(std::fs::File::create(&xml)?);
+        std::io::copy(&mut decoder, &mut out).context("Failed to extract dump")?;
+        out.flush()?;
+        Ok(())
+    })
+    .await??;
+    std::fs::remove_file(&gz_path)?;
+
+    std::path::absolute(&xml_path).context("Invalid dump path")
+}
+
+/// Run one of the workspace binaries inside its crate directory, so it loads
+/// that crate's `.env` just as `cargo run` there would.
+async fn run_service(bin_dir: &Path, name: &str, args: &[&str]) -> Result<()> {
+    let bin = bin_dir.join(format!("{}{}", name, std::env::consts::EXE_SUFFIX));
+    if !bin.is_file() {
+        bail!("{} not found; build the workspace first (`cargo build --release`)", bin.display());
+    }
+    let status = Command::new(&bin)
+        .args(args)
+        .current_dir(name)
+        .status()
+        .await
+        .with_context(|| format!("Failed to start {}", name))?;
+    if !status.success() {
+        bail!("{} exited with {}", name, status);
+    }
+    Ok(())
+}
+
+/// Fail the run before anything is published if the import broke a quality
+/// rule; `--threshold` overrides apply as for `cscs check`.
+async fn run_checks(config: &Config, args: &[String]) -> Result<()> {
+    let pool = db::connect(&config.database_url, 2).await?;
+    let results = check::run_rules(&pool, args).await?;
+    check::enforce(&results)
+}
+
+/// Runs after enrichment so new abstracts count; `--recent`, `--baseline`,
+/// `--min-count` and `--top` apply as for `cscs trends`. A failure is recorded
+/// as skipped and the refresh goes on.
+async fn run_trends(config: &Config, args: &[String]) -> Result<()> {
+    let pool = db::connect(&config.database_url, 2).await?;
+    trends::compute(&pool, args).await
+}
+
+/// Sync already bumps the cache generation; doing it again is harmless and
+/// makes Redis being down during sync show up as a failed stage here.
+async fn invalidate_cache(config: &Config) -> Result<()> {
+    let generation = cache::bump_generation(&config.redis_url).await?;
+    println!("Search cache generation is now {}", generation);
+    Ok(())
+}
+
+/// Remove a downloaded dump; it is several GB and the next run fetches a new one.
+fn cleanup(dump: Option<&Path>) -> Result<()> {
+    if let Some(dump) = dump
+        && dump.exists()
+    {
+        std::fs::remove_file(dump).with_context(|| format!("Failed to remove {}", dump.display()))?;
+    }
+    Ok(())
+}
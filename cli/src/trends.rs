@@ -0,0 +1,266 @@
+//! `cscs trends`: rank title and abstract n-grams by how much their share of
+//! papers grew in the last few years compared with the years before.
+//!
+//!     cscs trends [--recent <years>] [--baseline <years>] [--min-count <n>] [--top <n>] [--json <path>]
+//!
+//! Each paper counts once per n-gram (1 to 3 words, never spanning stopwords
+//! or punctuation), and counts are divided by the number of papers in each
+//! window, so growth in venue output alone doesn't look like a trend. The
+//! report is stored in `trend_reports` for the backend's `/trends/ngrams`.
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+
+use crate::arg_value;
+use cscs_core::{Config, db};
+
+const DEFAULT_RECENT_YEARS: i32 = 3;
+const DEFAULT_BASELINE_YEARS: i32 = 5;
+/// Papers in the recent window an n-gram needs before it can trend.
+const DEFAULT_MIN_COUNT: u32 = 10;
+const DEFAULT_TOP: usize = 200;
+const MAX_NGRAM: usize = 3;
+/// A shorter n-gram is dropped in favour of a longer trending one containing
+/// it when the longer one accounts for at least this share of its papers
+/// ("language models" under "large language models").
+const SUBSUME_SHARE: f64 = 0.8;
+
+const STOPWORDS: &[&str] = &[
+    "a", "about", "above", "across", "after", "against", "all", "also", "an", "and", "any", "are",
+    "as", "at", "be", "been", "being", "between", "both", "but", "by", "can", "could", "do",
+    "does", "each", "either", "for", "from", "further", "has", "have", "how", "however", "if",
+    "in", "into", "is", "it", "its", "itself", "may", "more", "most", "much", "must", "new",
+    "no", "not", "novel", "of", "on", "one", "only", "or", "other", "our", "over", "paper",
+    "propose", "proposed", "provide", "provides", "show", "shows", "such", "than", "that", "the",
+    "their", "them", "then", "there", "these", "they", "this", "those", "through", "to",
+    "towards", "two", "under", "up", "upon", "us", "use", "used", "uses", "using", "via", "was",
+    "we", "well", "were", "what", "when", "where", "which", "while", "who", "whose", "why",
+    "will", "with", "within", "without", "would", "yet",
+];
+
+#[derive(Serialize)]
+struct Trend {
+    ngram: String,
+    recent_count: u32,
+    baseline_count: u32,
+    recent_share: f64,
+    baseline_share: f64,
+    /// Recent share over baseline share, with the baseline count smoothed by
+    /// one so n-grams absent before don't divide by zero.
+    growth: f64,
+}
+
+/// Distinct n-grams of one paper's title and abstract.
+fn ngrams(text: &str) -> HashSet<String> {
+    let mut out = HashSet::new();
+    let lower = text.to_lowercase();
+    // Sentences and clauses first, so n-grams don't run across them.
+    for clause in lower.split(|c: char| ".,;:!?()[]{}\"".contains(c)) {
+        let words = clause
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty());
+        let mut run: Vec<&str> = Vec::new();
+        for word in words.chain(std::iter::once("")) {
+            let keep = word.chars().count() > 1
+                && !word.chars().all(|c| c.is_ascii_digit())
+                && !STOPWORDS.contains(&word);
+            if keep {
+                run.push(word);
+                continue;
+            }
+            for n in 1..=MAX_NGRAM.min(run.len()) {
+                for gram in run.windows(n) {
+                    out.insert(gram.join(" "));
+                }
+            }
+            run.clear();
+        }
+    }
+    out
+}
+
+/// Count the papers of `years` containing each n-gram; with `only`, count just
+/// those n-grams. Returns the counts and the number of papers.
+async fn count(
+    pool: &PgPool,
+    years: std::ops::RangeInclusive<i32>,
+    only: Option<&HashMap<String, u32>>,
+) -> Result<(HashMap<String, u32>, u32)> {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    let mut papers = 0;
+    // One year at a time keeps abstracts for only that year in memory.
+    for year in years {
+        let texts: Vec<String> = sqlx::query_scalar(
+            "SELECT title || '. ' || COALESCE(abstract, '') FROM papers \
+             WHERE year = $1 AND paper_type <> 'front_matter'"
+        )
+        .bind(year)
+        .fetch_all(pool)
+        .await
+        .with_context(|| format!("Failed to load papers of {}", year))?;
+        papers += texts.len() as u32;
+
+        for text in &texts {
+            for gram in ngrams(text) {
+                if only.is_none_or(|o| o.contains_key(&gram)) {
+                    *counts.entry(gram).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+    Ok((counts, papers))
+}
+
+/// Drop n-grams that mostly occur as part of a longer, also trending, n-gram.
+fn drop_subsumed(trends: Vec<Trend>) -> Vec<Trend> {
+    let longer: Vec<(String, u32)> = trends
+        .iter()
+        .filter(|t| t.ngram.contains(' '))
+        .map(|t| (format!(" {} ", t.ngram), t.recent_count))
+        .collect();
+    trends
+        .into_iter()
+        .filter(|t| {
+            let padded = format!(" {} ", t.ngram);
+            !longer.iter().any(|(l, count)| {
+                l.len() > padded.len() && l.contains(&padded) && *count as f64 >= SUBSUME_SHARE * t.recent_count as f64
+            })
+        })
+        .collect()
+}
+
+fn parse_arg<T: std::str::FromStr>(args: &[String], flag: &str, default: T) -> Result<T> {
+    arg_value(args, flag)
+        .map(|v| v.parse().ok().with_context(|| format!("{} must be a whole number", flag)))
+        .transpose()
+        .map(|v| v.unwrap_or(default))
+}
+
+/// Compute a report, store it and print the top of it.
+pub async fn compute(pool: &PgPool, args: &[String]) -> Result<()> {
+    let recent_years = parse_arg(args, "--recent", DEFAULT_RECENT_YEARS)?;
+    let baseline_years = parse_arg(args, "--baseline", DEFAULT_BASELINE_YEARS)?;
+    let min_count = parse_arg(args, "--min-count", DEFAULT_MIN_COUNT)?;
+    let top = parse_arg(args, "--top", DEFAULT_TOP)?;
+    if recent_years < 1 || baseline_years < 1 {
+        bail!("--recent and --baseline must be at least one year");
+    }
+
+    let latest: Option<i32> = sqlx::query_scalar("SELECT max(year) FROM papers WHERE year > 0")
+        .fetch_one(pool)
+        .await?;
+    let Some(latest) = latest else {
+        bail!("No papers with a year; import a dump first");
+    };
+    let recent = (latest - recent_years + 1)..=latest;
+    let baseline = (recent.start() - baseline_years)..=(recent.start() - 1);
+
+    // Only n-grams frequent enough in the recent window are counted in the
+    // baseline, which keeps the second pass small.
+    let (mut recent_counts, recent_papers) = count(pool, recent.clone(), None).await?;
+    recent_counts.retain(|_, c| *c >= min_count);
+    let (baseline_counts, baseline_papers) = count(pool, baseline.clone(), Some(&recent_counts)).await?;
+    if recent_papers == 0 || baseline_papers == 0 {
+        bail!(
+            "Need papers in both {}-{} and {}-{}",
+            baseline.start(), baseline.end(), recent.start(), recent.end()
+        );
+    }
+
+    let mut trends: Vec<Trend> = recent_counts
+        .into_iter()
+        .map(|(ngram, recent_count)| {
+            let baseline_count = baseline_counts.get(&ngram).copied().unwrap_or(0);
+            let recent_share = recent_count as f64 / recent_papers as f64;
+            let baseline_share = baseline_count as f64 / baseline_papers as f64;
+            let smoothed = (baseline_count + 1) as f64 / baseline_papers as f64;
+            Trend {
+                ngram,
+                recent_count,
+                baseline_count,
+                recent_share,
+                baseline_share,
+                growth: recent_share / smoothed,
+            }
+        })
+        .filter(|t| t.growth > 1.0)
+        .collect();
+    trends.sort_by(|a, b| b.growth.total_cmp(&a.growth).then_with(|| a.ngram.cmp(&b.ngram)));
+    // Subsumption only matters among candidates that could make the cut.
+    trends.truncate(top.saturating_mul(4));
+    let mut trends = drop_subsumed(trends);
+    trends.truncate(top);
+
+    let ngrams = serde_json::to_value(&trends)?;
+    sqlx::query(
+        "INSERT INTO trend_reports (recent_from, recent_to, baseline_from, baseline_to, \
+                                    recent_papers, baseline_papers, ngrams) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7)"
+    )
+    .bind(recent.start())
+    .bind(recent.end())
+    .bind(baseline.start())
+    .bind(baseline.end())
+    .bind(recent_papers as i32)
+    .bind(baseline_papers as i32)
+    .bind(&ngrams)
+    .execute(pool)
+    .await?;
+
+    println!(
+        "{} trending n-grams in {}-{} ({} papers) vs {}-{} ({} papers)",
+        trends.len(), recent.start(), recent.end(), recent_papers, baseline.start(), baseline.end(), baseline_papers
+    );
+    for t in trends.iter().take(20) {
+        println!("  {:>6.1}x  {:<40} {:>6} vs {:>6}", t.growth, t.ngram, t.recent_count, t.baseline_count);
+    }
+    if let Some(path) = arg_value(args, "--json") {
+        std::fs::write(&path, serde_json::to_string_pretty(&ngrams)?)
+            .with_context(|| format!("Failed to write {}", path))?;
+    }
+    Ok(())
+}
+
+pub async fn run(args: &[String]) -> Result<()> {
+    let config = Config::from_env()?;
+    db::check(&config.database_url).await?;
+    let pool = db::connect(&config.database_url, 2).await?;
+    compute(&pool, args).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ngrams_stop_at_stopwords_and_punctuation() {
+        let grams = ngrams("Serving Large Language Models. Using GPUs for inference");
+        assert!(grams.contains("large language models"));
+        assert!(grams.contains("serving large language"));
+        assert!(grams.contains("gpus"));
+        assert!(!grams.contains("models gpus"));
+        assert!(!grams.contains("using"));
+        assert!(!grams.contains("gpus inference"));
+    }
+
+    #[test]
+    fn shorter_ngrams_inside_longer_trends_are_dropped() {
+        let trend = |ngram: &str, recent_count| Trend {
+            ngram: ngram.into(),
+            recent_count,
+            baseline_count: 0,
+            recent_share: 0.0,
+            baseline_share: 0.0,
+            growth: 2.0,
+        };
+        let kept = drop_subsumed(vec![
+            trend("large language models", 90),
+            trend("language models", 100),
+            trend("models", 400),
+        ]);
+        let names: Vec<&str> = kept.iter().map(|t| t.ngram.as_str()).collect();
+        assert_eq!(names, ["large language models", "models"]);
+    }
+}
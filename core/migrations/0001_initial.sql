@@ -1,3 +1,6 @@
+-- Baseline schema. Statements in the early migrations are idempotent so that
+-- databases created from the old schema.sql can adopt the migration history.
+
 CREATE TABLE IF NOT EXISTS venues (
     id SERIAL PRIMARY KEY,
     raw_name TEXT UNIQUE NOT NULL
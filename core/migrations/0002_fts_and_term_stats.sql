@@ -0,0 +1,10 @@
+CREATE INDEX IF NOT EXISTS idx_papers_fts ON papers
+    USING GIN (to_tsvector('english', title || ' ' || COALESCE(abstract, '')));
+
+-- Document frequency per lexeme, used as IDF by the backend's similar-papers
+-- fallback. Refreshed by sync after each import.
+CREATE MATERIALIZED VIEW IF NOT EXISTS term_stats AS
+    SELECT word, ndoc
+    FROM ts_stat($$SELECT to_tsvector('english', title || ' ' || COALESCE(abstract, '')) FROM papers$$);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_term_stats_word ON term_stats(word);
@@ -0,0 +1,8 @@
+-- Citation edges between indexed papers, from Semantic Scholar's `references`.
+CREATE TABLE IF NOT EXISTS citations (
+    citing_paper_id INT REFERENCES papers(id) ON DELETE CASCADE,
+    cited_paper_id INT REFERENCES papers(id) ON DELETE CASCADE,
+    PRIMARY KEY (citing_paper_id, cited_paper_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_citations_cited ON citations(cited_paper_id);
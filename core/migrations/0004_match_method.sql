@@ -0,0 +1,4 @@
+-- How the parser matched enrichment data to a paper: 'doi', or 'title' for
+-- papers without a DOI (with the title-match score as confidence).
+ALTER TABLE papers ADD COLUMN IF NOT EXISTS match_method TEXT;
+ALTER TABLE papers ADD COLUMN IF NOT EXISTS match_confidence REAL;
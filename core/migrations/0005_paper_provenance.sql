@@ -0,0 +1,13 @@
+-- Where each enriched field of a paper came from and when it was fetched, so
+-- refreshes can target stale rows (parser `--max-age-days`).
+CREATE TABLE IF NOT EXISTS paper_provenance (
+    paper_id INT REFERENCES papers(id) ON DELETE CASCADE,
+    field TEXT NOT NULL CHECK (field IN ('citation_count', 'abstract')),
+    provider TEXT NOT NULL,
+    external_id TEXT,
+    match_method TEXT,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (paper_id, field)
+);
+
+CREATE INDEX IF NOT EXISTS idx_paper_provenance_fetched ON paper_provenance(field, fetched_at);
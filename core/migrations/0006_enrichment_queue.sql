@@ -0,0 +1,13 @@
+-- Papers awaiting enrichment, filled by `parser --enqueue` and drained by
+-- `parser --worker`. A row is deleted once its batch has been written back.
+CREATE TABLE IF NOT EXISTS enrichment_queue (
+    paper_id INT PRIMARY KEY REFERENCES papers(id) ON DELETE CASCADE,
+    want_citations BOOLEAN NOT NULL,
+    want_abstract BOOLEAN NOT NULL,
+    enqueued_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    attempts INT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    locked_until TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_enrichment_queue_enqueued ON enrichment_queue(enqueued_at);
@@ -0,0 +1,15 @@
+-- DOI extracted from the DBLP `<ee>` links, and every such link with its kind
+-- ('doi', 'arxiv', 'acm', 'ieee', ...). `papers.ee_link` keeps the preferred one.
+ALTER TABLE papers ADD COLUMN IF NOT EXISTS doi TEXT;
+CREATE INDEX IF NOT EXISTS idx_papers_doi ON papers(doi);
+
+CREATE TABLE IF NOT EXISTS paper_links (
+    paper_id INT REFERENCES papers(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    PRIMARY KEY (paper_id, url)
+);
+
+-- 'dblp' for links from (or derived from) the dump, otherwise the enrichment
+-- provider that found the open-access PDF. Re-imports only replace 'dblp' rows.
+ALTER TABLE paper_links ADD COLUMN IF NOT EXISTS source TEXT NOT NULL DEFAULT 'dblp';
@@ -0,0 +1,20 @@
+-- Same normalisation as the parser's title matcher: lowercase, punctuation to
+-- spaces, whitespace collapsed.
+CREATE OR REPLACE FUNCTION normalize_title(t TEXT) RETURNS TEXT
+    IMMUTABLE LANGUAGE SQL
+    AS $$ SELECT btrim(regexp_replace(lower(t), '[^[:alnum:]]+', ' ', 'g')) $$;
+
+-- arXiv id of the paper, from its own `<ee>` links or a matching CoRR record.
+ALTER TABLE papers ADD COLUMN IF NOT EXISTS arxiv_id TEXT;
+CREATE INDEX IF NOT EXISTS idx_papers_arxiv_id ON papers(arxiv_id);
+
+-- DBLP `journals/corr` records, kept only to link preprints to conference papers.
+CREATE TABLE IF NOT EXISTS corr_preprints (
+    dblp_key TEXT PRIMARY KEY,
+    arxiv_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    first_author TEXT,
+    year INT
+);
+
+CREATE INDEX IF NOT EXISTS idx_corr_preprints_title ON corr_preprints(normalize_title(title));
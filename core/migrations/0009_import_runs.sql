@@ -0,0 +1,15 @@
+-- One row per completed DBLP import, describing what changed relative to the
+-- database before the import. `report` holds the full diff (see the parser's
+-- `diff` module); the counts are copied out for cheap listing.
+CREATE TABLE IF NOT EXISTS import_runs (
+    id SERIAL PRIMARY KEY,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    dump_path TEXT NOT NULL,
+    papers_total INT NOT NULL,
+    papers_added INT NOT NULL,
+    papers_removed INT NOT NULL,
+    papers_retitled INT NOT NULL,
+    authors_changed INT NOT NULL,
+    report JSONB NOT NULL
+);
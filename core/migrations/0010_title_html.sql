@@ -0,0 +1,8 @@
+-- Display form of the title with DBLP's inline markup (<i>, <sub>, <sup>,
+-- <code>) kept and all text HTML-escaped; `title` stays plain for search.
+-- Existing rows get their escaped plain title until the next import.
+ALTER TABLE papers ADD COLUMN IF NOT EXISTS title_html TEXT;
+UPDATE papers
+SET title_html = replace(replace(replace(title, '&', '&amp;'), '<', '&lt;'), '>', '&gt;')
+WHERE title_html IS NULL;
+ALTER TABLE papers ALTER COLUMN title_html SET NOT NULL;
@@ -0,0 +1,15 @@
+-- Bibliographic fields DBLP records alongside title and year. `page_count` is
+-- derived from `pages` by the parser (NULL when the range can't be read) and
+-- is what short-vs-full paper filtering uses. `crossref` is the DBLP key of
+-- the proceedings volume the paper appears in.
+ALTER TABLE papers
+    ADD COLUMN IF NOT EXISTS pages TEXT,
+    ADD COLUMN IF NOT EXISTS page_count INT,
+    ADD COLUMN IF NOT EXISTS volume TEXT,
+    ADD COLUMN IF NOT EXISTS number TEXT,
+    ADD COLUMN IF NOT EXISTS crossref TEXT,
+    ADD COLUMN IF NOT EXISTS note TEXT,
+    ADD COLUMN IF NOT EXISTS mdate DATE;
+
+CREATE INDEX IF NOT EXISTS idx_papers_crossref ON papers(crossref);
+CREATE INDEX IF NOT EXISTS idx_papers_page_count ON papers(page_count);
@@ -0,0 +1,7 @@
+-- Kind of contribution, classified by the parser: full, short, poster, demo,
+-- keynote or front_matter. Existing rows count as full papers until the next
+-- import classifies them.
+ALTER TABLE papers ADD COLUMN IF NOT EXISTS paper_type TEXT NOT NULL DEFAULT 'full'
+    CHECK (paper_type IN ('full', 'short', 'poster', 'demo', 'keynote', 'front_matter'));
+
+CREATE INDEX IF NOT EXISTS idx_papers_paper_type ON papers(paper_type);
@@ -0,0 +1,15 @@
+-- One row per DBLP `<proceedings>` record of an indexed venue, e.g. OSDI 2022.
+-- Papers point at their edition through `papers.crossref` = `dblp_key`; a
+-- venue can have several editions (volumes) in one year.
+CREATE TABLE IF NOT EXISTS venue_editions (
+    id SERIAL PRIMARY KEY,
+    dblp_key TEXT NOT NULL UNIQUE,
+    venue_id INT NOT NULL REFERENCES venues(id),
+    year INT,
+    title TEXT NOT NULL,
+    location TEXT,
+    publisher TEXT,
+    isbn TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_venue_editions_venue_year ON venue_editions(venue_id, year);
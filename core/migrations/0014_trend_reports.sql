@@ -0,0 +1,14 @@
+-- Output of `cscs trends`: title/abstract n-grams whose share of papers grew
+-- the most in the recent window compared with the baseline window before it.
+-- `ngrams` is the ranked list; the backend serves the newest row.
+CREATE TABLE IF NOT EXISTS trend_reports (
+    id SERIAL PRIMARY KEY,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    recent_from INT NOT NULL,
+    recent_to INT NOT NULL,
+    baseline_from INT NOT NULL,
+    baseline_to INT NOT NULL,
+    recent_papers INT NOT NULL,
+    baseline_papers INT NOT NULL,
+    ngrams JSONB NOT NULL
+);
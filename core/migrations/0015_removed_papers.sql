@@ -0,0 +1,8 @@
+-- Papers deleted because a new DBLP dump no longer contains them (see the
+-- parser's `diff` module). `sync` removes them from the search index and then
+-- clears the rows.
+CREATE TABLE IF NOT EXISTS removed_papers (
+    paper_id INT PRIMARY KEY,
+    dblp_key TEXT NOT NULL,
+    removed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
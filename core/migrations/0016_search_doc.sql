@@ -0,0 +1,14 @@
+-- Stored title+abstract tsvector, so the similar-papers fallback and trend
+-- counts read lexemes and their frequencies instead of re-parsing every row.
+-- Replaces the expression index from 0002.
+ALTER TABLE papers ADD COLUMN IF NOT EXISTS search_doc TSVECTOR
+    GENERATED ALWAYS AS (to_tsvector('english', title || ' ' || COALESCE(abstract, ''))) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_papers_search_doc ON papers USING GIN (search_doc);
+DROP INDEX IF EXISTS idx_papers_fts;
+
+DROP MATERIALIZED VIEW IF EXISTS term_stats;
+CREATE MATERIALIZED VIEW term_stats AS
+    SELECT word, ndoc FROM ts_stat($$SELECT search_doc FROM papers$$);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_term_stats_word ON term_stats(word);
@@ -0,0 +1,39 @@
+//! Keys for the backend's response cache in Redis.
+//!
+//! Search responses are stored under `search:<generation>:<params hash>` and
+//! venue statistics under `stats:<generation>:<venue>`. Sync starts a new
+//! generation after each successful index run, so responses computed against
+//! the old data are never read again and simply age out via their TTL; nothing
+//! else in the Redis instance is touched.
+
+use anyhow::{Context, Result};
+use redis::AsyncCommands;
+use redis::aio::MultiplexedConnection;
+
+/// Counter holding the current cache generation.
+pub const GENERATION_KEY: &str = "search:generation";
+
+pub fn search_key(generation: u64, params_hash: &str) -> String {
+    format!("search:{}:{}", generation, params_hash)
+}
+
+pub fn stats_key(generation: u64, venue: &str) -> String {
+    format!("stats:{}:{}", generation, venue)
+}
+
+/// Current generation; 0 until the first bump.
+pub async fn generation(conn: &mut MultiplexedConnection) -> redis::RedisResult<u64> {
+    let generation: Option<u64> = conn.get(GENERATION_KEY).await?;
+    Ok(generation.unwrap_or(0))
+}
+
+/// Start a new generation, making every cached response unreachable.
+pub async fn bump_generation(redis_url: &str) -> Result<u64> {
+    let client = redis::Client::open(redis_url)?;
+    let mut conn = client
+        .get_multiplexed_async_connection()
+        .await
+        .context("Failed to connect to Redis")?;
+    let generation: u64 = conn.incr(GENERATION_KEY, 1).await?;
+    Ok(generation)
+}
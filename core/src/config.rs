@@ -0,0 +1,31 @@
+use anyhow::{Context, Result};
+
+/// Connection settings read from the environment (and `.env`, if present).
+/// Service-specific knobs such as `ENRICH_PROVIDERS` or `EMBEDDER_URL` stay
+/// with the code that uses them.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub database_url: String,
+    pub meili_url: String,
+    pub meili_master_key: Option<String>,
+    pub redis_url: String,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self> {
+        dotenvy::dotenv().ok();
+        Ok(Self {
+            database_url: std::env::var("DATABASE_URL").context("DATABASE_URL must be set")?,
+            meili_url: std::env::var("MEILI_URL").unwrap_or_else(|_| "http://localhost:7700".into()),
+            meili_master_key: std::env::var("MEILI_MASTER_KEY").ok().filter(|k| !k.is_empty()),
+            redis_url: std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".into()),
+        })
+    }
+
+    /// The Meilisearch key, for services that talk to Meilisearch.
+    pub fn meili_key(&self) -> Result<&str> {
+        self.meili_master_key
+            .as_deref()
+            .context("MEILI_MASTER_KEY must be set")
+    }
+}
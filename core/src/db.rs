@@ -0,0 +1,89 @@
+//! Postgres connections and the versioned schema. Migrations live in
+//! `core/migrations/` as `<version>_<name>.sql` and are embedded at compile
+//! time; applied versions are tracked in `_sqlx_migrations`.
+//!
+//! `migrate` and `check` use their own connection so they can run before the
+//! service's pool is sized and opened.
+
+use anyhow::{Context, Result, bail};
+use sqlx::Connection;
+use sqlx::migrate::Migrator;
+use sqlx::postgres::{PgConnection, PgPool, PgPoolOptions};
+
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Open a connection pool to `database_url`.
+pub async fn connect(database_url: &str, max_connections: u32) -> Result<PgPool> {
+    PgPoolOptions::new()
+        .max_connections(max_connections)
+        .connect(database_url)
+        .await
+        .context("Failed to connect to Postgres")
+}
+
+/// Newest schema version this build knows about.
+pub fn latest_version() -> i64 {
+    MIGRATOR.iter().map(|m| m.version).max().unwrap_or(0)
+}
+
+/// Apply pending migrations. Refuses to touch a database that has versions
+/// this build doesn't know (i.e. was migrated by a newer build) or whose
+/// applied migrations were edited afterwards.
+pub async fn migrate(database_url: &str) -> Result<()> {
+    let mut conn = PgConnection::connect(database_url)
+        .await
+        .context("Failed to connect to Postgres for migrations")?;
+    MIGRATOR.run(&mut conn).await.with_context(|| {
+        format!("Failed to migrate database schema (this build knows up to version {})", latest_version())
+    })?;
+    conn.close().await?;
+    Ok(())
+}
+
+/// Verify the database is at exactly the schema version of this build,
+/// without changing anything. For read-only services that shouldn't migrate.
+pub async fn check(database_url: &str) -> Result<()> {
+    let mut conn = PgConnection::connect(database_url)
+        .await
+        .context("Failed to connect to Postgres for schema check")?;
+
+    let has_table: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(&mut conn)
+        .await?;
+    if !has_table {
+        bail!("Database has no schema version; run `parser --migrate` first");
+    }
+
+    let applied: Vec<(i64, Vec<u8>, bool)> =
+        sqlx::query_as("SELECT version, checksum, success FROM _sqlx_migrations ORDER BY version")
+            .fetch_all(&mut conn)
+            .await?;
+    conn.close().await?;
+
+    for (version, checksum, success) in &applied {
+        if !success {
+            bail!("Migration {} failed partway; fix the database before continuing", version);
+        }
+        match MIGRATOR.iter().find(|m| m.version == *version) {
+            None => bail!(
+                "Database schema version {} is unknown to this build (latest known: {}); upgrade it",
+                version,
+                latest_version()
+            ),
+            Some(m) if m.checksum.as_ref() != checksum.as_slice() => {
+                bail!("Migration {} was modified after being applied", version)
+            }
+            Some(_) => {}
+        }
+    }
+
+    let current = applied.last().map(|a| a.0).unwrap_or(0);
+    if current < latest_version() {
+        bail!(
+            "Database schema is at version {} but this build expects {}; run `parser --migrate`",
+            current,
+            latest_version()
+        );
+    }
+    Ok(())
+}
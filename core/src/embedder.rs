@@ -0,0 +1,111 @@
+use anyhow::Context;
+use serde::Deserialize;
+
+/// Name of the Meilisearch embedder the vectors are stored under. The backend
+/// queries the same name when running hybrid searches.
+pub const EMBEDDER_NAME: &str = "default";
+
+/// Upper bound on a document batch; CPU-served models can take a while per chunk.
+const BATCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+/// A search embeds its query inline, so give up quickly and let the caller fall
+/// back to keyword search rather than hang the request.
+const QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Client for any OpenAI-compatible `/v1/embeddings` endpoint. Ollama,
+/// text-embeddings-inference and llama.cpp all expose one, so a small model such
+/// as `bge-small-en-v1.5` can be served on CPU next to the rest of the stack.
+/// Sync embeds documents and the backend embeds queries with the same model.
+#[derive(Clone)]
+pub struct Embedder {
+    client: reqwest::Client,
+    url: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl Embedder {
+    /// Build an embedder from `EMBEDDER_URL` / `EMBEDDER_MODEL` / `EMBEDDER_API_KEY`.
+    /// Returns `None` when no URL is configured, in which case sync indexes
+    /// documents without vectors and hybrid requests fall back to keyword search.
+    pub fn from_env() -> Option<Self> {
+        let url = std::env::var("EMBEDDER_URL").ok().filter(|u| !u.is_empty())?;
+        let model = std::env::var("EMBEDDER_MODEL").unwrap_or_else(|_| "bge-small-en-v1.5".into());
+        let api_key = std::env::var("EMBEDDER_API_KEY").ok().filter(|k| !k.is_empty());
+        // reqwest::Client::new() panics the same way if the TLS backend fails.
+        let client = reqwest::Client::builder()
+            .timeout(BATCH_TIMEOUT)
+            .build()
+            .expect("Failed to build the embedder HTTP client");
+        Some(Self {
+            client,
+            url,
+            model,
+            api_key,
+        })
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Embed `texts`, returning one vector per input in the same order.
+    pub async fn embed(&self, texts: &[String]) -> anyhow::Result<Vec<Vec<f32>>> {
+        self.embed_within(texts, BATCH_TIMEOUT).await
+    }
+
+    async fn embed_within(
+        &self,
+        texts: &[String],
+        timeout: std::time::Duration,
+    ) -> anyhow::Result<Vec<Vec<f32>>> {
+        let mut req = self
+            .client
+            .post(&self.url)
+            .timeout(timeout)
+            .json(&serde_json::json!({ "model": self.model, "input": texts }));
+        if let Some(ref key) = self.api_key {
+            req = req.bearer_auth(key);
+        }
+
+        let resp = req
+            .send()
+            .await
+            .context("Embedding request failed")?
+            .error_for_status()?;
+        let mut parsed: EmbeddingResponse = resp.json().await?;
+        parsed.data.sort_by_key(|d| d.index);
+
+        if parsed.data.len() != texts.len() {
+            anyhow::bail!(
+                "Embedder returned {} vectors for {} inputs",
+                parsed.data.len(),
+                texts.len()
+            );
+        }
+        Ok(parsed.data.into_iter().map(|d| d.embedding).collect())
+    }
+
+    /// Embed a single search query, failing after a few seconds.
+    pub async fn embed_query(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+        let mut vectors = self.embed_within(&[text.to_string()], QUERY_TIMEOUT).await?;
+        vectors.pop().context("Embedder returned no vectors")
+    }
+
+    /// Embed a probe string to discover the model's output dimensions, which
+    /// Meilisearch needs up front for a `userProvided` embedder.
+    pub async fn dimensions(&self) -> anyhow::Result<usize> {
+        let probe = self.embed(&["dimension probe".to_string()]).await?;
+        Ok(probe.first().map(|v| v.len()).unwrap_or(0))
+    }
+}
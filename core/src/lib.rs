@@ -0,0 +1,15 @@
+//! Code shared by the parser, sync and backend: the paper record as indexed
+//! and served, the venue registry, Postgres access and schema migrations, and
+//! configuration. Adding a field to `PaperDoc` and to the query in `papers`
+//! is enough for it to reach both Meilisearch and the API.
+
+pub mod cache;
+pub mod config;
+pub mod db;
+pub mod embedder;
+pub mod models;
+pub mod papers;
+pub mod venues;
+
+pub use config::Config;
+pub use models::{PaperDoc, PaperLink, PaperType};
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A paper as sync indexes it in Meilisearch and the backend serves it. Rows
+/// come from `papers::fetch_*`, so both sides always agree on the fields.
+#[derive(Serialize, Deserialize, Debug, sqlx::FromRow)]
+pub struct PaperDoc {
+    pub id: i32,
+    pub title: String,
+    /// `title` with DBLP's inline markup as escaped, render-safe HTML.
+    #[serde(default)]
+    pub title_html: String,
+    pub year: i32,
+    pub venue: String,
+    pub authors: Vec<String>,
+    pub ee_link: Option<String>,
+    pub dblp_key: String,
+    pub citation_count: i32,
+    #[serde(default)]
+    pub abstract_text: Option<String>,
+    #[serde(default)]
+    pub doi: Option<String>,
+    #[serde(default)]
+    pub arxiv_id: Option<String>,
+    #[serde(default)]
+    #[sqlx(json)]
+    pub links: Vec<PaperLink>,
+    /// Best open-access PDF among `links`, if any.
+    #[serde(default)]
+    pub pdf_url: Option<String>,
+    #[serde(default)]
+    pub open_access: bool,
+    /// DBLP `<pages>` as given, e.g. "123-135" or "12:1-12:24".
+    #[serde(default)]
+    pub pages: Option<String>,
+    /// Pages covered by `pages`, when they could be read.
+    #[serde(default)]
+    pub page_count: Option<i32>,
+    #[serde(default)]
+    pub volume: Option<String>,
+    #[serde(default)]
+    pub number: Option<String>,
+    /// DBLP key of the proceedings volume, e.g. "conf/osdi/2022".
+    #[serde(default)]
+    pub crossref: Option<String>,
+    /// DBLP `<note>`s such as award annotations, joined with "; ".
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Date DBLP last modified the record, as YYYY-MM-DD.
+    #[serde(default)]
+    pub mdate: Option<String>,
+    /// `PaperType` name: "full", "short", "poster", "demo", "keynote" or "front_matter".
+    #[serde(default)]
+    pub paper_type: String,
+    /// Embeddings keyed by Meilisearch embedder name; only set while indexing.
+    #[serde(rename = "_vectors", default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(skip)]
+    pub vectors: Option<HashMap<String, Vec<f32>>>,
+}
+
+/// One DBLP electronic-edition link; `kind` is e.g. "doi", "arxiv" or "acm".
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PaperLink {
+    pub url: String,
+    pub kind: String,
+}
+
+/// What kind of contribution an entry is, as classified by the parser from
+/// page counts, the proceedings it appears in and its title. Search shows
+/// `Full` papers unless asked otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaperType {
+    Full,
+    Short,
+    Poster,
+    Demo,
+    Keynote,
+    FrontMatter,
+}
+
+impl PaperType {
+    pub const ALL: [PaperType; 6] = [
+        PaperType::Full,
+        PaperType::Short,
+        PaperType::Poster,
+        PaperType::Demo,
+        PaperType::Keynote,
+        PaperType::FrontMatter,
+    ];
+
+    /// Name stored in `papers.paper_type` and accepted by `/search`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PaperType::Full => "full",
+            PaperType::Short => "short",
+            PaperType::Poster => "poster",
+            PaperType::Demo => "demo",
+            PaperType::Keynote => "keynote",
+            PaperType::FrontMatter => "front_matter",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<PaperType> {
+        PaperType::ALL.into_iter().find(|t| t.as_str() == name)
+    }
+}
@@ -0,0 +1,58 @@
+use sqlx::PgPool;
+
+use crate::models::PaperDoc;
+
+/// Every column of `PaperDoc`. Callers append a WHERE clause; the GROUP BY is
+/// added by `query`.
+const SELECT_PAPER_DOC: &str = "\
+    SELECT p.id, p.title, p.title_html, COALESCE(p.year, 0) AS year, v.raw_name AS venue, \
+           COALESCE(ARRAY_AGG(a.name ORDER BY pa.author_order) FILTER (WHERE a.name IS NOT NULL), '{}') AS authors, \
+           p.ee_link, p.dblp_key, COALESCE(p.citation_count, 0) AS citation_count, p.abstract AS abstract_text, p.doi, p.arxiv_id, \
+           (SELECT COALESCE(json_agg(json_build_object('url', l.url, 'kind', l.kind) ORDER BY l.url), '[]') \
+            FROM paper_links l WHERE l.paper_id = p.id) AS links, \
+           (SELECT l.url FROM paper_links l WHERE l.paper_id = p.id AND l.kind = 'pdf' \
+            ORDER BY l.source = 'dblp' DESC, l.url LIMIT 1) AS pdf_url, \
+           EXISTS (SELECT 1 FROM paper_links l WHERE l.paper_id = p.id AND l.kind IN ('pdf', 'usenix')) AS open_access, \
+           p.pages, p.page_count, p.volume, p.number, p.crossref, p.note, p.mdate::text AS mdate, p.paper_type \
+    FROM papers p \
+    JOIN venues v ON p.venue_id = v.id \
+    LEFT JOIN paper_authors pa ON pa.paper_id = p.id \
+    LEFT JOIN authors a ON a.id = pa.author_id";
+
+fn query(filter: &str) -> String {
+    format!("{} {} GROUP BY p.id, v.raw_name", SELECT_PAPER_DOC, filter)
+}
+
+/// Load papers by id, preserving the order of `ids`. Unknown ids are skipped.
+pub async fn fetch_by_ids(db: &PgPool, ids: &[i32]) -> Result<Vec<PaperDoc>, sqlx::Error> {
+    let rows: Vec<PaperDoc> = sqlx::query_as(&query("WHERE p.id = ANY($1)"))
+        .bind(ids)
+        .fetch_all(db)
+        .await?;
+
+    let mut by_id: std::collections::HashMap<i32, PaperDoc> =
+        rows.into_iter().map(|r| (r.id, r)).collect();
+    Ok(ids.iter().filter_map(|id| by_id.remove(id)).collect())
+}
+
+/// Keyset page of papers with `id > after_id`, in id order.
+pub async fn fetch_after(db: &PgPool, after_id: i32, limit: i64) -> Result<Vec<PaperDoc>, sqlx::Error> {
+    sqlx::query_as(&format!("{} ORDER BY p.id LIMIT $2", query("WHERE p.id > $1")))
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(db)
+        .await
+}
+
+/// Every paper of `venue` (canonical name) in `year`, grouped by proceedings
+/// volume and in page order within each, as printed in the program.
+pub async fn fetch_by_venue_year(db: &PgPool, venue: &str, year: i32) -> Result<Vec<PaperDoc>, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "{} ORDER BY p.crossref NULLS LAST, substring(p.pages FROM '^[0-9]+')::int NULLS LAST, p.id",
+        query("WHERE v.raw_name = $1 AND p.year = $2")
+    ))
+    .bind(venue)
+    .bind(year)
+    .fetch_all(db)
+    .await
+}
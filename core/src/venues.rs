@@ -0,0 +1,125 @@
+//! The venues we index, keyed by DBLP's `conf/<short name>/...` key prefix.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Canonical display name followed by the DBLP short names that map to it.
+const VENUES: &[&[&str]] = &[
+    &["AAAI", "aaai"],
+    &["ACL", "acl"],
+    &["ASE", "kbse"],
+    &["ASPLOS", "asplos"],
+    &["CAV", "cav"],
+    &["CCS", "ccs"],
+    &["CHI", "chi"],
+    &["CIKM", "cikm"],
+    &["COLT", "colt"],
+    &["CoNEXT", "conext"],
+    &["CRYPTO", "crypto"],
+    &["CVPR", "cvpr"],
+    &["DAC", "dac"],
+    &["ECCV", "eccv"],
+    &["EMNLP", "emnlp"],
+    &["EUROCRYPT", "eurocrypt"],
+    &["EuroSys", "eurosys"],
+    &["FAST", "fast"],
+    &["FOCS", "focs"],
+    &["FSE", "sigsoft"],
+    &["HPCA", "hpca"],
+    &["HPDC", "hpdc"],
+    &["ICCV", "iccv"],
+    &["ICDE", "icde"],
+    &["ICDM", "icdm"],
+    &["ICFP", "icfp"],
+    &["ICLR", "iclr"],
+    &["ICML", "icml"],
+    &["ICSE", "icse"],
+    &["INFOCOM", "infocom"],
+    &["ISCA", "isca"],
+    &["ISSTA", "issta"],
+    &["KDD", "kdd"],
+    &["LICS", "lics"],
+    &["MICCAI", "miccai"],
+    &["MICRO", "micro"],
+    &["MobiCom", "mobicom"],
+    &["MobiSys", "mobisys"],
+    &["NAACL", "naacl"],
+    &["NDSS", "ndss"],
+    &["NeurIPS", "nips"],
+    &["NSDI", "nsdi"],
+    &["OOPSLA", "oopsla"],
+    &["OSDI", "osdi"],
+    &["PACT", "IEEEpact"],
+    &["PLDI", "pldi"],
+    &["PODC", "podc"],
+    &["PODS", "pods"],
+    &["POPL", "popl"],
+    &["PPoPP", "ppopp"],
+    &["RTAS", "rtas"],
+    &["RTSS", "rtss"],
+    &["S&P", "sp"],
+    &["SC", "sc"],
+    &["SenSys", "sensys"],
+    &["SIGCOMM", "sigcomm"],
+    &["SIGGRAPH", "siggraph"],
+    &["SIGGRAPH Asia", "siggrapha"],
+    &["SIGIR", "sigir"],
+    &["SIGMETRICS", "sigmetrics"],
+    &["SIGMOD", "sigmod"],
+    &["SoCG", "compgeom"],
+    &["SODA", "soda"],
+    &["SOSP", "sosp"],
+    &["STOC", "stoc"],
+    &["TACAS", "tacas"],
+    &["UbiComp", "huc"],
+    &["UIST", "uist"],
+    &["USENIX ATC", "usenix"],
+    &["USENIX Security", "uss"],
+    &["VIS", "visualization"],
+    &["VLDB", "vldb"],
+    &["VR", "vr"],
+    &["WWW", "www"],
+];
+
+/// Lowercased alias (short name or canonical name) -> canonical name.
+static VENUE_MAP: LazyLock<HashMap<String, &'static str>> = LazyLock::new(|| {
+    let mut m = HashMap::new();
+    for group in VENUES {
+        let canonical_name = group[0];
+        for alias in group.iter() {
+            m.insert(alias.to_lowercase(), canonical_name);
+        }
+    }
+    m
+});
+
+/// Canonical names of all indexed venues, in registry order.
+pub fn canonical_names() -> impl Iterator<Item = &'static str> {
+    VENUES.iter().map(|group| group[0])
+}
+
+/// Canonical name for a DBLP short name or a canonical name in any case.
+pub fn canonical_name(alias: &str) -> Option<&'static str> {
+    VENUE_MAP.get(&alias.to_lowercase()).copied()
+}
+
+/// Whether a DBLP key belongs to one of the venues we index, so references to
+/// papers outside the index can be dropped before touching Postgres.
+pub fn is_indexed_key(dblp_key: &str) -> bool {
+    let mut parts = dblp_key.split('/');
+    match (parts.next(), parts.next()) {
+        (Some("conf"), Some(short_name)) => canonical_name(short_name).is_some(),
+        _ => false,
+    }
+}
+
+/// Canonical venue for a DBLP conference entry, or `None` if the venue isn't
+/// indexed or the entry belongs to a co-located workshop.
+pub fn canonical_venue(dblp_key: &str, booktitle: &str, title: &str) -> Option<&'static str> {
+    let short_name = dblp_key.strip_prefix("conf/")?.split('/').next()?;
+    let canonical = canonical_name(short_name)?;
+    if booktitle.to_lowercase().contains("workshop") || title.to_lowercase().contains("workshop") {
+        return None;
+    }
+    Some(canonical)
+}
@@ -13,11 +13,13 @@ const CORS_HEADERS: Record<string, string> = {
 
 const MAX_LIMIT = 100;
 const DEFAULT_LIMIT = 20;
+const PAPER_TYPES = ['full', 'short', 'poster', 'demo', 'keynote', 'front_matter', 'all'];
 
 // Fields returned by the backend for each hit (see backend PaperHit).
 interface BackendHit {
     id: number;
     title: string;
+    title_html?: string;
     authors: string[];
     venue: string;
     year: number;
@@ -25,6 +27,18 @@ interface BackendHit {
     dblp_key: string;
     citation_count?: number;
     abstract_text?: string | null;
+    doi?: string | null;
+    arxiv_id?: string | null;
+    links?: { url: string; kind: string }[];
+    pdf_url?: string | null;
+    open_access?: boolean;
+    pages?: string | null;
+    page_count?: number | null;
+    volume?: string | null;
+    number?: string | null;
+    crossref?: string | null;
+    note?: string | null;
+    paper_type?: string;
 }
 
 // Preflight for cross-origin browser clients.
@@ -42,6 +56,11 @@ export async function GET(request: NextRequest) {
     const rawLimit = parseInt(inParams.get('limit') ?? String(DEFAULT_LIMIT), 10) || DEFAULT_LIMIT;
     const limit = Math.min(MAX_LIMIT, Math.max(1, rawLimit));
     const sort = inParams.get('sort');
+    const mode = inParams.get('mode');
+    const semanticRatio = inParams.get('semantic_ratio');
+    const openAccess = inParams.get('open_access');
+    const minPages = inParams.get('min_pages');
+    const maxPages = inParams.get('max_pages');
 
     // Only forward a known, safe subset of params to the backend.
     if (q) targetUrl.searchParams.set('q', q);
@@ -50,6 +69,14 @@ export async function GET(request: NextRequest) {
     targetUrl.searchParams.set('page', String(page));
     targetUrl.searchParams.set('limit', String(limit));
     if (sort) targetUrl.searchParams.set('sort', sort);
+    if (mode) targetUrl.searchParams.set('mode', mode);
+    if (semanticRatio) targetUrl.searchParams.set('semantic_ratio', semanticRatio);
+    if (openAccess === 'true' || openAccess === 'false') targetUrl.searchParams.set('open_access', openAccess);
+    if (minPages && /^\d+$/.test(minPages)) targetUrl.searchParams.set('min_pages', minPages);
+    if (maxPages && /^\d+$/.test(maxPages)) targetUrl.searchParams.set('max_pages', maxPages);
+    inParams.getAll('paper_type')
+        .filter((t) => PAPER_TYPES.includes(t))
+        .forEach((t) => targetUrl.searchParams.append('paper_type', t));
 
     try {
         const res = await fetch(targetUrl.toString(), {
@@ -70,6 +97,7 @@ export async function GET(request: NextRequest) {
             ? data.hits.map((h: BackendHit) => ({
                 id: h.id,
                 title: h.title,
+                title_html: h.title_html || null,
                 authors: h.authors,
                 venue: h.venue,
                 year: h.year,
@@ -77,6 +105,18 @@ export async function GET(request: NextRequest) {
                 url: h.ee_link ?? null,
                 dblp_key: h.dblp_key,
                 abstract: h.abstract_text ?? null,
+                doi: h.doi ?? null,
+                arxiv_id: h.arxiv_id ?? null,
+                links: h.links ?? [],
+                pdf_url: h.pdf_url ?? null,
+                open_access: h.open_access ?? false,
+                pages: h.pages ?? null,
+                page_count: h.page_count ?? null,
+                volume: h.volume ?? null,
+                number: h.number ?? null,
+                crossref: h.crossref ?? null,
+                note: h.note ?? null,
+                paper_type: h.paper_type || 'full',
             }))
             : [];
 
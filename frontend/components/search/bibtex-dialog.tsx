@@ -87,11 +87,23 @@ export function BibtexDialog({ paper }: { paper: Paper }) {
 
 export function generateFallbackBibtex(paper: Paper) {
     const dblpKey = paper.dblp_key || paper._formatted?.dblp_key
+    const fields: [string, string | number | null | undefined][] = [
+        ['author', paper.authors.join(' and ')],
+        ['title', paper.title],
+        ['booktitle', paper.venue],
+        ['volume', paper.volume],
+        ['number', paper.number],
+        // BibTeX wants an en dash between page numbers.
+        ['pages', paper.pages?.replace(/-+/g, '--')],
+        ['year', paper.year],
+        ['url', paper.ee_link],
+        ['doi', paper.doi],
+    ]
+    const body = fields
+        .filter(([, value]) => value !== null && value !== undefined && value !== '')
+        .map(([name, value]) => `  ${name.padEnd(9)} = {${value}}`)
+        .join(',\n')
     return `@inproceedings{${dblpKey || `conf/${paper.venue.toLowerCase()}/${paper.year}`},
-  author    = {${paper.authors.join(' and ')}},
-  title     = {${paper.title}},
-  booktitle = {${paper.venue}},
-  year      = {${paper.year}},
-  url       = {${paper.ee_link}}
+${body}
 }`
 }
@@ -38,6 +38,12 @@ export function FilterSidebar() {
                     >
                         Relevance
                     </button>
+                    <button
+                        onClick={() => setSort('relevance_boosted')}
+                        className={cn("text-sm text-left px-2 py-1.5 rounded-md transition-colors", sort === 'relevance_boosted' ? "bg-primary/10 font-medium text-primary" : "text-muted-foreground hover:text-foreground hover:bg-accent/50")}
+                    >
+                        Relevance + Citations
+                    </button>
                     <button
                         onClick={() => setSort('year')}
                         className={cn("text-sm text-left px-2 py-1.5 rounded-md transition-colors", sort === 'year' ? "bg-primary/10 font-medium text-primary" : "text-muted-foreground hover:text-foreground hover:bg-accent/50")}
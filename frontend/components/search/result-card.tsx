@@ -10,6 +10,10 @@ import { BibtexDialog } from './bibtex-dialog'
 export function ResultCard({ paper }: { paper: Paper }) {
     const [q, setQ] = useQueryState('q')
     const isSearchActive = q && q.trim().length > 0;
+    // Highlighted matches win; otherwise show the title with its markup (k-means in italics, O(n²)).
+    const titleHtml = paper._formatted?.title?.includes('<em>')
+        ? paper._formatted.title
+        : paper.title_html || paper._formatted?.title || paper.title
 
     return (
         <article>
@@ -21,9 +25,7 @@ export function ResultCard({ paper }: { paper: Paper }) {
                             target="_blank"
                             rel="noopener noreferrer"
                             className="hover:underline decoration-primary decoration-2 underline-offset-4 text-primary"
-                            dangerouslySetInnerHTML={{
-                                __html: paper._formatted?.title || paper.title
-                            }}
+                            dangerouslySetInnerHTML={{ __html: titleHtml }}
                         />
                     </CardTitle>
                 </CardHeader>
@@ -65,6 +67,16 @@ export function ResultCard({ paper }: { paper: Paper }) {
                             Cited by {paper._formatted?.citation_count || paper.citation_count || 0}
                         </span>
                         <div className="ml-auto flex items-center gap-2">
+                            {paper.pdf_url && (
+                                <a
+                                    href={paper.pdf_url}
+                                    target="_blank"
+                                    rel="noopener noreferrer"
+                                    className="text-muted-foreground hover:text-foreground hover:underline"
+                                >
+                                    PDF
+                                </a>
+                            )}
                             <BibtexDialog paper={paper} />
                         </div>
                     </div>
@@ -1,6 +1,12 @@
+export interface PaperLink {
+    url: string
+    kind: string
+}
+
 export interface Paper {
     id: number
     title: string
+    title_html?: string
     authors: string[]
     venue: string
     year: number
@@ -8,6 +14,18 @@ export interface Paper {
     dblp_key: string
     citation_count?: number
     abstract_text?: string
+    doi?: string | null
+    arxiv_id?: string | null
+    links?: PaperLink[]
+    pdf_url?: string | null
+    open_access?: boolean
+    pages?: string | null
+    page_count?: number | null
+    volume?: string | null
+    number?: string | null
+    crossref?: string | null
+    note?: string | null
+    paper_type?: string
     _formatted?: {
         title: string
         venue: string
@@ -0,0 +1,179 @@
+//! Bibliographic fields of a DBLP record beyond title, authors, venue and year.
+//! They are stored as DBLP has them, for BibTeX; only `<pages>` is interpreted,
+//! into a page count for short-vs-full paper filtering.
+
+/// `<pages>`, `<volume>`, `<number>`, `<crossref>`, `<note>` and the record's
+/// `mdate` attribute, plus `<publisher>` and `<isbn>` of proceedings records.
+/// Repeated `<note>`s are joined with "; ".
+#[derive(Default)]
+pub struct Bib {
+    pub pages: Option<String>,
+    pub volume: Option<String>,
+    pub number: Option<String>,
+    pub crossref: Option<String>,
+    pub note: Option<String>,
+    pub mdate: Option<String>,
+    pub publisher: Option<String>,
+    pub isbn: Option<String>,
+}
+
+impl Bib {
+    /// Append text read inside `tag`; other tags are ignored.
+    pub fn push_text(&mut self, tag: &str, text: &str) {
+        let field = match tag {
+            "pages" => &mut self.pages,
+            "volume" => &mut self.volume,
+            "number" => &mut self.number,
+            "crossref" => &mut self.crossref,
+            "note" => &mut self.note,
+            "publisher" => &mut self.publisher,
+            "isbn" => &mut self.isbn,
+            _ => return,
+        };
+        field.get_or_insert_with(String::new).push_str(text);
+    }
+
+    /// A new `<note>` starts; separate it from the previous one.
+    pub fn start_note(&mut self) {
+        if let Some(note) = self.note.as_mut().filter(|n| !n.trim().is_empty()) {
+            note.push_str("; ");
+        }
+    }
+
+    /// Collapse whitespace and drop fields that turned out empty.
+    pub fn tidy(&mut self) {
+        for field in [
+            &mut self.pages,
+            &mut self.volume,
+            &mut self.number,
+            &mut self.crossref,
+            &mut self.note,
+            &mut self.publisher,
+            &mut self.isbn,
+        ] {
+            *field = field
+                .take()
+                .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+                .map(|s| s.trim_end_matches(';').trim().to_string())
+                .filter(|s| !s.is_empty());
+        }
+        if let Some(pages) = self.pages.as_mut() {
+            *pages = pages.replace(" -", "-").replace("- ", "-");
+        }
+    }
+
+    pub fn page_count(&self) -> Option<i32> {
+        self.pages.as_deref().and_then(page_count)
+    }
+}
+
+/// Number of pages covered by a DBLP `<pages>` value, or `None` if it can't be
+/// read. Values come as "123-135", "5", "12:1-12:24" (article-numbered
+/// journals), "i-xii" (front matter) and occasionally lists like "1-4, 7-9".
+pub fn page_count(pages: &str) -> Option<i32> {
+    let mut total = 0;
+    for part in pages.split(',') {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        let count = match part.split_once('-') {
+            Some((first, last)) => {
+                let first = page_number(first)?;
+                let last = page_number(last)?;
+                if last < first {
+                    return None;
+                }
+                last - first + 1
+            }
+            None => {
+                page_number(part)?;
+                1
+            }
+        };
+        total += count;
+    }
+    (total > 0).then_some(total)
+}
+
+/// "135" -> 135; "12:24" (article 12, page 24) -> 24; roman numerals -> value.
+fn page_number(page: &str) -> Option<i32> {
+    let page = page.trim();
+    let page = page.rsplit_once(':').map_or(page, |(_, p)| p);
+    page.parse().ok().or_else(|| roman(page))
+}
+
+fn roman(s: &str) -> Option<i32> {
+    if s.is_empty() {
+        return None;
+    }
+    let mut total = 0;
+    let mut prev = 0;
+    for c in s.chars().rev() {
+        let v = match c.to_ascii_lowercase() {
+            'i' => 1,
+            'v' => 5,
+            'x' => 10,
+            'l' => 50,
+            'c' => 100,
+            'd' => 500,
+            'm' => 1000,
+            _ => return None,
+        };
+        if v < prev {
+            total -= v;
+        } else {
+            total += v;
+            prev = v;
+        }
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_range() {
+        assert_eq!(page_count("123-135"), Some(13));
+    }
+
+    #[test]
+    fn single_page() {
+        assert_eq!(page_count("5"), Some(1));
+    }
+
+    #[test]
+    fn article_numbered_range() {
+        assert_eq!(page_count("12:1-12:24"), Some(24));
+    }
+
+    #[test]
+    fn roman_front_matter() {
+        assert_eq!(page_count("i-xii"), Some(12));
+    }
+
+    #[test]
+    fn list_of_ranges_is_summed() {
+        assert_eq!(page_count("1-4, 7-9"), Some(7));
+    }
+
+    #[test]
+    fn repeated_notes_are_joined() {
+        let mut bib = Bib::default();
+        bib.start_note();
+        bib.push_text("note", "Best Paper Award");
+        bib.start_note();
+        bib.push_text("note", " Artifact\n evaluated ");
+        bib.tidy();
+        assert_eq!(bib.note.as_deref(), Some("Best Paper Award; Artifact evaluated"));
+    }
+
+    #[test]
+    fn unreadable_values_give_none() {
+        assert_eq!(page_count(""), None);
+        assert_eq!(page_count("135-123"), None);
+        assert_eq!(page_count("forthcoming"), None);
+    }
+}
@@ -0,0 +1,205 @@
+//! Paper type classification. Top venues publish posters, demos, keynote
+//! abstracts and front matter in the same DBLP stream as research papers; the
+//! signals are the proceedings the entry belongs to (its `<booktitle>` and
+//! `<crossref>` key), title conventions and, failing those, the page count of
+//! an explicit page range.
+
+use cscs_core::PaperType;
+
+use crate::bib::Bib;
+
+/// At most this many pages without any other signal: a poster or extended abstract.
+const POSTER_MAX_PAGES: i32 = 2;
+/// At most this many pages: a short paper.
+const SHORT_MAX_PAGES: i32 = 5;
+
+/// Titles (lowercase, trailing period dropped) of proceedings front matter.
+const FRONT_MATTER_PREFIXES: &[&str] = &[
+    "front matter",
+    "frontmatter",
+    "preface",
+    "foreword",
+    "message from",
+    "welcome message",
+    "welcome from the",
+    "table of contents",
+    "session details",
+    "author index",
+    "organizing committee",
+    "program committee",
+    "conference organization",
+    "editorial",
+];
+
+/// Front matter titles that are also how real papers begin ("Welcome to the
+/// Machine: ..."), so only the whole title counts.
+const FRONT_MATTER_TITLES: &[&str] = &["welcome", "proceedings"];
+
+const KEYNOTE_PREFIXES: &[&str] = &["keynote", "invited talk", "invited paper:", "plenary talk"];
+
+/// Track names: whole words of a `<booktitle>` ("SIGMOD Conference (Demos)")
+/// or the suffix after the year of a `<crossref>` volume key
+/// ("conf/sigcomm/2019posters").
+const TRACKS: &[(&str, PaperType)] = &[
+    ("keynote", PaperType::Keynote),
+    ("keynotes", PaperType::Keynote),
+    ("demo", PaperType::Demo),
+    ("demos", PaperType::Demo),
+    ("demonstration", PaperType::Demo),
+    ("demonstrations", PaperType::Demo),
+    ("poster", PaperType::Poster),
+    ("posters", PaperType::Poster),
+    ("short", PaperType::Short),
+];
+
+/// `booktitle` is DBLP's raw `<booktitle>`, before venue canonicalisation.
+pub fn classify(title: &str, booktitle: &str, bib: &Bib) -> PaperType {
+    let title = title.trim().trim_end_matches('.').to_lowercase();
+    let in_track = |kind: PaperType| proceedings_track(booktitle, bib.crossref.as_deref(), kind);
+    let pages = bib.pages.as_deref().unwrap_or("");
+
+    // Roman page numbers are only used for the proceedings' own preamble.
+    let roman_pages = !pages.is_empty() && !pages.chars().any(|c| c.is_ascii_digit());
+    if roman_pages
+        || FRONT_MATTER_PREFIXES.iter().any(|p| title.starts_with(p))
+        || FRONT_MATTER_TITLES.contains(&title.as_str())
+    {
+        return PaperType::FrontMatter;
+    }
+    if in_track(PaperType::Keynote)
+        || KEYNOTE_PREFIXES.iter().any(|p| title.starts_with(p))
+        || title.contains("(keynote")
+    {
+        return PaperType::Keynote;
+    }
+    if in_track(PaperType::Demo) || has_marker(&title, &["demo", "demonstration"]) {
+        return PaperType::Demo;
+    }
+    if in_track(PaperType::Poster) || has_marker(&title, &["poster", "extended abstract"]) {
+        return PaperType::Poster;
+    }
+    if in_track(PaperType::Short) || has_marker(&title, &["short paper"]) {
+        return PaperType::Short;
+    }
+    // A single page number ("5") is where the paper starts, not its length.
+    if !pages.contains('-') {
+        return PaperType::Full;
+    }
+    match bib.page_count() {
+        Some(n) if n <= POSTER_MAX_PAGES => PaperType::Poster,
+        Some(n) if n <= SHORT_MAX_PAGES => PaperType::Short,
+        _ => PaperType::Full,
+    }
+}
+
+/// Whether the booktitle or crossref volume names a `kind` track.
+fn proceedings_track(booktitle: &str, crossref: Option<&str>, kind: PaperType) -> bool {
+    let booktitle = booktitle.to_lowercase();
+    let suffix = crossref
+        .and_then(|c| c.rsplit('/').next())
+        .unwrap_or("")
+        .trim_start_matches(|c: char| c.is_ascii_digit())
+        .to_lowercase();
+    TRACKS.iter().filter(|(_, t)| *t == kind).any(|(name, _)| {
+        suffix == *name || booktitle.split(|c: char| !c.is_alphanumeric()).any(|w| w == *name)
+    })
+}
+
+/// "Poster: ...", "Demo - ..." or "... (Poster)".
+fn has_marker(title: &str, markers: &[&str]) -> bool {
+    markers.iter().any(|m| {
+        title
+            .strip_prefix(m)
+            .is_some_and(|rest| rest.trim_start().starts_with([':', '-']))
+            || title.contains(&format!("({}", m))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bib(pages: &str, crossref: &str) -> Bib {
+        Bib {
+            pages: (!pages.is_empty()).then(|| pages.to_string()),
+            crossref: (!crossref.is_empty()).then(|| crossref.to_string()),
+            ..Bib::default()
+        }
+    }
+
+    #[test]
+    fn long_paper_is_full() {
+        let t = classify("In Search of an Understandable Consensus Algorithm.", "USENIX ATC", &bib("305-319", "conf/usenix/2014"));
+        assert_eq!(t, PaperType::Full);
+    }
+
+    #[test]
+    fn missing_pages_default_to_full() {
+        assert_eq!(classify("Spanner.", "OSDI", &bib("", "")), PaperType::Full);
+    }
+
+    #[test]
+    fn page_count_separates_posters_and_short_papers() {
+        assert_eq!(classify("A Study.", "SIGCOMM", &bib("1-2", "")), PaperType::Poster);
+        assert_eq!(classify("A Study.", "SIGCOMM", &bib("1-4", "")), PaperType::Short);
+    }
+
+    #[test]
+    fn single_page_number_is_not_a_length() {
+        assert_eq!(classify("A Study.", "SIGCOMM", &bib("5", "")), PaperType::Full);
+    }
+
+    #[test]
+    fn proceedings_name_wins_over_pages() {
+        let t = classify("Live Migration at Scale.", "SIGMOD Conference (Demos)", &bib("10-13", ""));
+        assert_eq!(t, PaperType::Demo);
+        let t = classify("Fast Paths.", "SIGCOMM", &bib("1-3", "conf/sigcomm/2019posters"));
+        assert_eq!(t, PaperType::Poster);
+    }
+
+    #[test]
+    fn track_names_must_be_whole_words() {
+        let t = classify("Consensus.", "DEMOCRITUS Workshop", &bib("1-3", "conf/democritus/2020"));
+        assert_eq!(t, PaperType::Short);
+        let t = classify("Consensus.", "ShortCuts Symposium", &bib("1-14", "conf/shortcuts/2020"));
+        assert_eq!(t, PaperType::Full);
+        let t = classify("Consensus.", "ACL (Short Papers)", &bib("1-14", ""));
+        assert_eq!(t, PaperType::Short);
+        let t = classify("Consensus.", "SIGMOD Conference", &bib("1-14", "conf/sigmod/2021demos"));
+        assert_eq!(t, PaperType::Demo);
+    }
+
+    #[test]
+    fn title_markers() {
+        assert_eq!(classify("Poster: Faster Joins.", "EuroSys", &bib("", "")), PaperType::Poster);
+        assert_eq!(classify("Demo - A Query Debugger.", "VLDB", &bib("", "")), PaperType::Demo);
+        assert_eq!(classify("Keynote: The Next Decade of Systems.", "SOSP", &bib("1", "")), PaperType::Keynote);
+        assert_eq!(classify("Front Matter.", "OSDI", &bib("", "")), PaperType::FrontMatter);
+    }
+
+    #[test]
+    fn marker_words_inside_titles_are_ignored() {
+        let t = classify("Demonstrating Consensus at Scale.", "NSDI", &bib("1-14", ""));
+        assert_eq!(t, PaperType::Full);
+    }
+
+    #[test]
+    fn front_matter_titles() {
+        for title in ["Welcome.", "Welcome Message from the General Chairs.", "Welcome from the Program Chairs.", "Proceedings."] {
+            assert_eq!(classify(title, "ICDE", &bib("", "")), PaperType::FrontMatter, "{}", title);
+        }
+    }
+
+    #[test]
+    fn papers_named_like_front_matter_are_not_front_matter() {
+        let t = classify("Welcome to the Machine: Exploring the Potential of Large Language Models.", "CHI", &bib("1-14", ""));
+        assert_eq!(t, PaperType::Full);
+        let t = classify("Proceedings of the Crowd: Community Review at Scale.", "CSCW", &bib("1-14", ""));
+        assert_eq!(t, PaperType::Full);
+    }
+
+    #[test]
+    fn roman_pages_are_front_matter() {
+        assert_eq!(classify("Conference Organizers.", "FAST", &bib("i-xii", "")), PaperType::FrontMatter);
+    }
+}
@@ -0,0 +1,251 @@
+//! Dump-to-dump diff. During an import every batch is compared with what
+//! Postgres holds for the same keys before it is written; papers the database
+//! has but the new dump no longer contains are found at the end and reported.
+//! With `--prune-missing` they are also deleted, so each removal is reported
+//! once. The report is stored in `import_runs` and written as JSON next to the
+//! dump (`--diff-out`).
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sqlx::{Pool, Postgres};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::time::Instant;
+
+use crate::Paper;
+
+/// Entries listed per kind of change; the counts are always exact. A first
+/// import "adds" every paper, which is not worth listing.
+const MAX_LISTED: usize = 1000;
+/// `--prune-missing` refuses to delete more than this share of the stored
+/// papers; a sample or filtered dump would otherwise wipe the database.
+const MAX_PRUNE_SHARE: f64 = 0.05;
+
+#[derive(Serialize)]
+pub struct Changes<T> {
+    count: usize,
+    truncated: bool,
+    entries: Vec<T>,
+}
+
+impl<T> Default for Changes<T> {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            truncated: false,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T> Changes<T> {
+    fn push(&mut self, entry: T) {
+        self.count += 1;
+        if self.entries.len() < MAX_LISTED {
+            self.entries.push(entry);
+        } else {
+            self.truncated = true;
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Retitled {
+    dblp_key: String,
+    old_title: String,
+    new_title: String,
+}
+
+#[derive(Serialize)]
+pub struct AuthorsChanged {
+    dblp_key: String,
+    old_authors: Vec<String>,
+    new_authors: Vec<String>,
+}
+
+/// Papers per venue before the import and in the new dump.
+#[derive(Serialize, Default)]
+pub struct VenueCounts {
+    previous: i64,
+    current: i64,
+    added: i64,
+    removed: i64,
+}
+
+#[derive(Serialize)]
+pub struct ImportDiff {
+    dump: String,
+    papers_total: usize,
+    added: Changes<String>,
+    removed: Changes<String>,
+    retitled: Changes<Retitled>,
+    authors_changed: Changes<AuthorsChanged>,
+    venues: BTreeMap<String, VenueCounts>,
+    /// Whether the removed papers were deleted (`--prune-missing`).
+    pruned: bool,
+    #[serde(skip)]
+    seen: HashSet<String>,
+    #[serde(skip)]
+    started: Instant,
+}
+
+impl ImportDiff {
+    /// Snapshot per-venue counts before anything from the new dump is written.
+    pub async fn start(pool: &Pool<Postgres>, dump: &str) -> Result<Self> {
+        let counts: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT v.raw_name, count(*) FROM papers p JOIN venues v ON v.id = p.venue_id GROUP BY v.raw_name"
+        )
+        .fetch_all(pool).await?;
+
+        let venues = counts
+            .into_iter()
+            .map(|(venue, previous)| (venue, VenueCounts { previous, ..VenueCounts::default() }))
+            .collect();
+
+        Ok(Self {
+            dump: dump.to_string(),
+            papers_total: 0,
+            added: Changes::default(),
+            removed: Changes::default(),
+            retitled: Changes::default(),
+            authors_changed: Changes::default(),
+            venues,
+            pruned: false,
+            seen: HashSet::new(),
+            started: Instant::now(),
+        })
+    }
+
+    /// Compare a batch with the stored rows for the same keys. Must run before
+    /// the batch is inserted.
+    pub async fn observe(&mut self, pool: &Pool<Postgres>, batch: &[Paper]) -> Result<()> {
+        let keys: Vec<&str> = batch.iter().map(|p| p.dblp_key.as_str()).collect();
+        let rows: Vec<(String, String, Vec<String>)> = sqlx::query_as(
+            "SELECT p.dblp_key, p.title, \
+                    ARRAY(SELECT a.name FROM paper_authors pa JOIN authors a ON a.id = pa.author_id \
+                          WHERE pa.paper_id = p.id ORDER BY pa.author_order) \
+             FROM papers p WHERE p.dblp_key = ANY($1)"
+        )
+        .bind(&keys)
+        .fetch_all(pool).await?;
+        let stored: HashMap<String, (String, Vec<String>)> =
+            rows.into_iter().map(|(key, title, authors)| (key, (title, authors))).collect();
+
+        for paper in batch {
+            if !self.seen.insert(paper.dblp_key.clone()) {
+                continue;
+            }
+            let venue = self.venues.entry(paper.venue.clone()).or_default();
+            venue.current += 1;
+
+            match stored.get(&paper.dblp_key) {
+                None => {
+                    venue.added += 1;
+                    self.added.push(paper.dblp_key.clone());
+                }
+                Some((title, authors)) => {
+                    if *title != paper.title {
+                        self.retitled.push(Retitled {
+                            dblp_key: paper.dblp_key.clone(),
+                            old_title: title.clone(),
+                            new_title: paper.title.clone(),
+                        });
+                    }
+                    if *authors != paper.authors {
+                        self.authors_changed.push(AuthorsChanged {
+                            dblp_key: paper.dblp_key.clone(),
+                            old_authors: authors.clone(),
+                            new_authors: paper.authors.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Find papers missing from the dump, then record the run in `import_runs`
+    /// and write the report to `out`. With `prune`, the missing papers are also
+    /// deleted (and recorded in `removed_papers` for `sync`) unless they are
+    /// more than `MAX_PRUNE_SHARE` of the stored papers. Only call this after
+    /// the whole dump was read; on a truncated dump everything after the break
+    /// would count as removed.
+    pub async fn finish(mut self, pool: &Pool<Postgres>, out: &Path, prune: bool) -> Result<()> {
+        let stored: i64 = self.venues.values().map(|v| v.previous).sum();
+        let seen: Vec<String> = self.seen.iter().cloned().collect();
+        let removed: Vec<(i32, String, String)> = sqlx::query_as(
+            "SELECT p.id, p.dblp_key, v.raw_name FROM papers p \
+             JOIN venues v ON v.id = p.venue_id \
+             LEFT JOIN UNNEST($1::text[]) AS s(dblp_key) ON s.dblp_key = p.dblp_key \
+             WHERE s.dblp_key IS NULL \
+             ORDER BY p.dblp_key"
+        )
+        .bind(&seen)
+        .fetch_all(pool).await?;
+        let removed_ids: Vec<i32> = removed.iter().map(|r| r.0).collect();
+        for (_, dblp_key, venue) in removed {
+            self.venues.entry(venue).or_default().removed += 1;
+            self.removed.push(dblp_key);
+        }
+        self.papers_total = self.seen.len();
+
+        let mut tx = pool.begin().await?;
+        if prune && !removed_ids.is_empty() {
+            let share = removed_ids.len() as f64 / stored.max(1) as f64;
+            if share > MAX_PRUNE_SHARE {
+                eprintln!(
+                    "Not pruning: {} of {} stored papers ({:.1}%) are missing from the dump, over the {:.0}% limit",
+                    removed_ids.len(),
+                    stored,
+                    share * 100.0,
+                    MAX_PRUNE_SHARE * 100.0
+                );
+            } else {
+                // Citations, authorships, links and queue rows cascade.
+                sqlx::query(
+                    "INSERT INTO removed_papers (paper_id, dblp_key) \
+                     SELECT id, dblp_key FROM papers WHERE id = ANY($1) \
+                     ON CONFLICT (paper_id) DO NOTHING"
+                )
+                .bind(&removed_ids)
+                .execute(&mut *tx).await?;
+                sqlx::query("DELETE FROM papers WHERE id = ANY($1)")
+                    .bind(&removed_ids)
+                    .execute(&mut *tx).await?;
+                self.pruned = true;
+            }
+        }
+
+        let report = serde_json::to_value(&self)?;
+        sqlx::query(
+            "INSERT INTO import_runs (started_at, dump_path, papers_total, papers_added, papers_removed, \
+                                      papers_retitled, authors_changed, report) \
+             VALUES (now() - make_interval(secs => $1), $2, $3, $4, $5, $6, $7, $8)"
+        )
+        .bind(self.started.elapsed().as_secs_f64())
+        .bind(&self.dump)
+        .bind(self.papers_total as i32)
+        .bind(self.added.count as i32)
+        .bind(self.removed.count as i32)
+        .bind(self.retitled.count as i32)
+        .bind(self.authors_changed.count as i32)
+        .bind(&report)
+        .execute(&mut *tx).await?;
+        tx.commit().await?;
+
+        std::fs::write(out, serde_json::to_string_pretty(&report)?)
+            .with_context(|| format!("Failed to write diff report {}", out.display()))?;
+
+        println!(
+            "\nImport diff: {} papers, {} added, {} removed{}, {} retitled, {} with changed authors (report: {})",
+            self.papers_total,
+            self.added.count,
+            self.removed.count,
+            if self.pruned { " (deleted)" } else { "" },
+            self.retitled.count,
+            self.authors_changed.count,
+            out.display()
+        );
+        Ok(())
+    }
+}
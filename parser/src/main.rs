@@ -1,122 +1,51 @@
 use anyhow::{Context, Result};
-use once_cell::sync::Lazy;
 use quick_xml::events::Event;
 use quick_xml::reader::Reader;
-use serde::Deserialize;
 use tokio::sync::mpsc;
-use sqlx::postgres::PgPoolOptions;
 use sqlx::{Pool, Postgres};
 use std::collections::HashMap;
 use std::env;
 use urlencoding::{decode, encode};
 
-static VENUE_MAP: Lazy<HashMap<String, String>> = Lazy::new(|| {
-    let mut m = HashMap::new();
-    let top_v_raw = vec![
-        vec!["AAAI", "aaai"],
-        vec!["ACL", "acl"],
-        vec!["ASE", "kbse"],
-        vec!["ASPLOS", "asplos"],
-        vec!["CAV", "cav"],
-        vec!["CCS", "ccs"],
-        vec!["CHI", "chi"],
-        vec!["CIKM", "cikm"],
-        vec!["COLT", "colt"],
-        vec!["CoNEXT", "conext"],
-        vec!["CRYPTO", "crypto"],
-        vec!["CVPR", "cvpr"],
-        vec!["DAC", "dac"],
-        vec!["ECCV", "eccv"],
-        vec!["EMNLP", "emnlp"],
-        vec!["EUROCRYPT", "eurocrypt"],
-        vec!["EuroSys", "eurosys"],
-        vec!["FAST", "fast"],
-        vec!["FOCS", "focs"],
-        vec!["FSE", "sigsoft"],
-        vec!["HPCA", "hpca"],
-        vec!["HPDC", "hpdc"],
-        vec!["ICCV", "iccv"],
-        vec!["ICDE", "icde"],
-        vec!["ICDM", "icdm"],
-        vec!["ICFP", "icfp"],
-        vec!["ICLR", "iclr"],
-        vec!["ICML", "icml"],
-        vec!["ICSE", "icse"],
-        vec!["INFOCOM", "infocom"],
-        vec!["ISCA", "isca"],
-        vec!["ISSTA", "issta"],
-        vec!["KDD", "kdd"],
-        vec!["LICS", "lics"],
-        vec!["MICCAI", "miccai"],
-        vec!["MICRO", "micro"],
-        vec!["MobiCom", "mobicom"],
-        vec!["MobiSys", "mobisys"],
-        vec!["NAACL", "naacl"],
-        vec!["NDSS", "ndss"],
-        vec!["NeurIPS", "nips"],
-        vec!["NSDI", "nsdi"],
-        vec!["OOPSLA", "oopsla"],
-        vec!["OSDI", "osdi"],
-        vec!["PACT", "IEEEpact"],
-        vec!["PLDI", "pldi"],
-        vec!["PODC", "podc"],
-        vec!["PODS", "pods"],
-        vec!["POPL", "popl"],
-        vec!["PPoPP", "ppopp"],
-        vec!["RTAS", "rtas"],
-        vec!["RTSS", "rtss"],
-        vec!["S&P", "sp"],
-        vec!["SC", "sc"],
-        vec!["SenSys", "sensys"],
-        vec!["SIGCOMM", "sigcomm"],
-        vec!["SIGGRAPH", "siggraph"],
-        vec!["SIGGRAPH Asia", "siggrapha"],
-        vec!["SIGIR", "sigir"],
-        vec!["SIGMETRICS", "sigmetrics"],
-        vec!["SIGMOD", "sigmod"],
-        vec!["SoCG", "compgeom"],
-        vec!["SODA", "soda"],
-        vec!["SOSP", "sosp"],
-        vec!["STOC", "stoc"],
-        vec!["TACAS", "tacas"],
-        vec!["UbiComp", "huc"],
-        vec!["UIST", "uist"],
-        vec!["USENIX ATC", "usenix"],
-        vec!["USENIX Security", "uss"],
-        vec!["VIS", "visualization"],
-        vec!["VLDB", "vldb"],
-        vec!["VR", "vr"],
-        vec!["WWW", "www"],
-    ];
-    for group in top_v_raw {
-        let canonical_name = group[0].to_string();
-        for alias in group {
-            m.insert(alias.to_lowercase(), canonical_name.clone());
-        }
-    }
-    m
-});
+mod bib;
+mod classify;
+mod diff;
+mod proceedings;
+mod providers;
+mod queue;
+#[cfg(test)]
+mod mock_server;
+
+use cscs_core::{db, venues, Config, PaperType};
+use providers::{title_match, EnrichmentProvider, LookupKey, Provenance, Want};
 
 struct Paper {
+    // Plain text for search and matching.
     title: String,
+    // Escaped HTML keeping DBLP's inline markup (italics, sub/superscripts).
+    title_html: String,
     year: i32,
     authors: Vec<String>,
     venue: String,
     dblp_key: String,
     ee_links: Vec<String>,
+    // Pages, volume, crossref, notes and mdate, kept for BibTeX.
+    bib: bib::Bib,
+    // Full paper, short paper, poster, ...; see `classify`.
+    paper_type: PaperType,
     citation_count: Option<i32>,
     abstract_text: Option<String>,
-}
-
-// Semantic Scholar's `/paper/batch` returns a JSON array positionally aligned
-// with the requested ids (null for unknown papers). Abstracts come back as
-// plain text, so no inverted-index reconstruction is needed.
-#[derive(Deserialize, Debug)]
-struct SemanticScholarPaper {
-    #[serde(rename = "citationCount")]
-    citation_count: Option<i32>,
-    #[serde(rename = "abstract")]
-    abstract_text: Option<String>,
+    // Open-access PDF found by a provider (`provenance.open_access_url` says which).
+    open_access_url: Option<String>,
+    // DBLP keys of indexed papers this one cites, from Semantic Scholar. Set
+    // only when a provider reported them this run; they then replace the
+    // paper's stored citation edges.
+    references: Option<Vec<String>>,
+    // How the enrichment was matched to this paper: "doi" or "title".
+    match_method: Option<&'static str>,
+    match_confidence: Option<f32>,
+    // Which provider supplied the citation count / abstract, for freshness.
+    provenance: Provenance,
 }
 
 async fn parse_args() -> Result<Vec<String>> {
@@ -124,25 +53,82 @@ async fn parse_args() -> Result<Vec<String>> {
     Ok(args)
 }
 
+/// Value following `flag` on the command line, e.g. `--s2-url http://...`.
+fn arg_value(args: &[String], flag: &str) -> Option<String> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = parse_args().await?;
 
-    dotenvy::dotenv().ok();
-    let db_url = env::var("DATABASE_URL").context("DATABASE_URL must be set")?;
-    let pool = PgPoolOptions::new()
-        .max_connections(20)
-        .connect(&db_url)
-        .await?;
-    parse_and_insert(&pool, "dblp.xml", args).await?;
+    let config = Config::from_env()?;
+    db::migrate(&config.database_url).await?;
+    if args.contains(&"--migrate".to_string()) {
+        println!("Database schema is at version {}", db::latest_version());
+        return Ok(());
+    }
+    let pool = db::connect(&config.database_url, 20).await?;
+    if args.contains(&"--worker".to_string()) {
+        queue::run_worker(&pool, &args).await?;
+    } else {
+        let dump = arg_value(&args, "--dump").unwrap_or_else(|| "dblp.xml".into());
+        parse_and_insert(&pool, &dump, args).await?;
+    }
     Ok(())
 }
 
+/// Providers are queried in priority order and their answers merged; pick them
+/// with `--providers a,b` or ENRICH_PROVIDERS (default: Semantic Scholar).
+/// `--s2-url <url>` overrides SEMANTIC_SCHOLAR_API_URL, e.g. to run against a mock.
+fn build_providers(args: &[String]) -> Result<Vec<Box<dyn EnrichmentProvider>>> {
+    let names = arg_value(args, "--providers")
+        .or_else(|| env::var("ENRICH_PROVIDERS").ok())
+        .unwrap_or_else(|| "semantic_scholar".into());
+    let providers = providers::from_names(&names, arg_value(args, "--s2-url"))?;
+    let names: Vec<&str> = providers.iter().map(|p| p.name()).collect();
+    println!("Enrichment providers: {}", names.join(", "));
+    Ok(providers)
+}
+
+/// Papers without a DOI are matched by title + year + first author unless
+/// `--no-title-match` is given. TITLE_MATCH_THRESHOLD sets the minimum
+/// confidence (0..1) for accepting a candidate.
+fn title_threshold(args: &[String]) -> Option<f64> {
+    if args.contains(&"--no-title-match".to_string()) {
+        return None;
+    }
+    Some(
+        env::var("TITLE_MATCH_THRESHOLD")
+            .ok()
+            .and_then(|t| t.parse().ok())
+            .unwrap_or(0.9),
+    )
+}
+
+/// `--max-age-days N` skips papers whose wanted fields were all fetched in
+/// the last N days (see `paper_provenance`), so repeated refreshes spend the
+/// rate-limit budget on stale rows.
+fn max_age_days(args: &[String]) -> Result<Option<i32>> {
+    arg_value(args, "--max-age-days")
+        .map(|d| d.parse().context("--max-age-days must be a whole number of days"))
+        .transpose()
+}
+
 async fn parse_and_insert(pool: &Pool<Postgres>, path: &str, args: Vec<String>) -> Result<()> {
     const TARGET_ENTRY_TAG: &str = "inproceedings";
-
-    let mut reader = Reader::from_file(path)?;
-    reader.trim_text(true);
+    // Journal articles are only read for CoRR (arXiv) preprints, which are
+    // matched to their conference versions after the import.
+    const CORR_ENTRY_TAG: &str = "article";
+    const CORR_KEY_PREFIX: &str = "journals/corr/";
+    // Proceedings volumes of indexed venues become `venue_editions`.
+    const PROCEEDINGS_ENTRY_TAG: &str = "proceedings";
+
+    let file = std::fs::File::open(path).with_context(|| format!("Failed to open DBLP dump {}", path))?;
+    let mut reader = dump_reader(std::io::BufReader::new(file));
     let mut buf = Vec::new();
     let mut current_paper: Option<Paper> = None;
     let mut current_tag = String::new();
@@ -160,39 +146,59 @@ async fn parse_and_insert(pool: &Pool<Postgres>, path: &str, args: Vec<String>)
         (has_cite, has_abstract, !update_only_cite)
     };
 
+    // `--enqueue` leaves enrichment to `--worker`: papers are only inserted and
+    // added to `enrichment_queue`, so a crash mid-enrichment doesn't mean
+    // re-parsing the dump.
+    let enqueue = args.contains(&"--enqueue".to_string());
+    let want = Want {
+        citations: enable_cite,
+        abstracts: enable_abstract,
+    };
+
     println!("Start Parsing...");
 
-    // Two-stage pipeline. The Semantic Scholar fetcher is the bottleneck (~1
-    // request/second), so it runs on its own globally-paced loop while the DB
-    // inserter works in parallel — the rate-limit budget is never spent waiting
-    // on Postgres. producer --raw--> fetcher --enriched--> inserter.
+    // Two-stage pipeline. The enrichment fetcher is the bottleneck (Semantic
+    // Scholar allows ~1 request/second), so it runs on its own rate-limited loop
+    // while the DB inserter works in parallel — the rate-limit budget is never
+    // spent waiting on Postgres. producer --raw--> fetcher --enriched--> inserter.
     let (tx, mut raw_rx) = mpsc::channel::<Vec<Paper>>(10);
     let (enriched_tx, mut enriched_rx) = mpsc::channel::<Vec<Paper>>(4);
 
-    let api_key = env::var("SEMANTIC_SCHOLAR_API_KEY").ok();
-    if (enable_cite || enable_abstract) && api_key.is_none() {
-        eprintln!("Warning: SEMANTIC_SCHOLAR_API_KEY not set; requests will be heavily rate-limited.");
-    }
+    let enrich_inline = (enable_cite || enable_abstract) && !enqueue;
+    let providers: Vec<Box<dyn EnrichmentProvider>> = if enrich_inline {
+        build_providers(&args)?
+    } else {
+        Vec::new()
+    };
+    let title_threshold = title_threshold(&args);
+
+    let max_age_days = max_age_days(&args)?;
+
+    // Full imports are diffed against the database as it was before this run;
+    // the report goes to `import_runs` and to `--diff-out` (import-diff.json).
+    let mut diff = if enable_insert {
+        Some(diff::ImportDiff::start(pool, path).await?)
+    } else {
+        None
+    };
+    let diff_out = arg_value(&args, "--diff-out").unwrap_or_else(|| "import-diff.json".into());
+    // `--prune-missing` deletes stored papers the dump no longer has; only
+    // meant for full DBLP dumps.
+    let prune_missing = args.contains(&"--prune-missing".to_string());
 
-    // Stage 1: enrich via Semantic Scholar, globally paced to 1 request/second.
+    // Stage 1: enrich via the configured providers, each paced by its own limiter.
     let fetch_pool = pool.clone();
     let fetcher_handle = tokio::spawn(async move {
-        let http_client = reqwest::Client::new();
-        // 1100ms leaves headroom under the 1 req/s limit; Delay keeps >=1 period
-        // between ticks even after a slow request/retry, so it never bursts.
-        let mut pacer = tokio::time::interval(tokio::time::Duration::from_millis(1100));
-        pacer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
-
         while let Some(mut batch) = raw_rx.recv().await {
-            if enable_cite || enable_abstract {
+            if enrich_inline {
                 if let Err(e) = enrich_batch(
                     &fetch_pool,
-                    &http_client,
-                    api_key.as_deref(),
+                    &providers,
                     &mut batch,
                     enable_cite,
                     enable_abstract,
-                    &mut pacer,
+                    title_threshold,
+                    max_age_days,
                 )
                 .await
                 {
@@ -208,44 +214,115 @@ async fn parse_and_insert(pool: &Pool<Postgres>, path: &str, args: Vec<String>)
     // Stage 2: persist to Postgres, concurrently with stage 1.
     let insert_pool = pool.clone();
     let inserter_handle = tokio::spawn(async move {
+        // Edges whose cited paper hasn't been inserted yet; retried at the end.
+        let mut pending_edges: Vec<(String, String)> = Vec::new();
+
         while let Some(mut batch) = enriched_rx.recv().await {
+            let citing = collect_citing(&batch);
+            let edges = collect_citation_edges(&batch);
+            let provenance = collect_provenance(&batch);
+            let oa_links = collect_oa_links(&batch);
+            let keys: Vec<String> = if enqueue {
+                batch.iter().map(|p| p.dblp_key.clone()).collect()
+            } else {
+                Vec::new()
+            };
             if enable_insert {
+                if let Some(ref mut d) = diff {
+                    if let Err(e) = d.observe(&insert_pool, &batch).await {
+                        eprintln!("Error diffing batch, skipping the diff report: {}", e);
+                        diff = None;
+                    }
+                }
                 if let Err(e) = insert_batch(&insert_pool, &mut batch).await {
                     eprintln!("Error inserting batch: {}", e);
                 }
-            } else if update_only_cite {
+            } else if update_only_cite && !enqueue {
                 if let Err(e) = update_citations_batch(&insert_pool, &batch).await {
                     eprintln!("Error updating citations: {}", e);
                 }
             }
+            match insert_citations(&insert_pool, &citing, &edges).await {
+                Ok(unresolved) => pending_edges.extend(unresolved),
+                Err(e) => eprintln!("Error inserting citations: {}", e),
+            }
+            if let Err(e) = upsert_provenance(&insert_pool, &provenance).await {
+                eprintln!("Error recording provenance: {}", e);
+            }
+            if let Err(e) = upsert_oa_links(&insert_pool, &oa_links).await {
+                eprintln!("Error recording open-access links: {}", e);
+            }
+            if enqueue {
+                if let Err(e) = queue::enqueue(&insert_pool, &keys, want).await {
+                    eprintln!("Error enqueueing batch: {}", e);
+                }
+            }
             print!(".");
             let _ = std::io::Write::flush(&mut std::io::stdout());
         }
+
+        if !pending_edges.is_empty() {
+            match insert_citations(&insert_pool, &[], &pending_edges).await {
+                Ok(unresolved) if !unresolved.is_empty() => {
+                    println!("\nDropped {} citation edges to papers not in the index", unresolved.len());
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Error inserting pending citations: {}", e),
+            }
+        }
+        diff
     });
 
     let mut batch: Vec<Paper> = Vec::with_capacity(1000);
+    let mut corr_batch: Vec<Paper> = Vec::new();
+    // A few thousand at most; written in one go after the papers.
+    let mut editions: Vec<Paper> = Vec::new();
+    // Set when reading stops early on malformed XML; the dump is then incomplete.
+    let mut truncated = false;
+    // quick-xml reports a file cut off mid-element as a plain Eof, so a dump
+    // is only complete once its root element is closed.
+    let mut root_closed = false;
 
     loop {
         match reader.read_event_into(&mut buf) {
             Ok(Event::Start(ref e)) => {
                 let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
                 match tag_name.as_str() {
-                    TARGET_ENTRY_TAG => {
+                    TARGET_ENTRY_TAG | CORR_ENTRY_TAG | PROCEEDINGS_ENTRY_TAG => {
                         let mut key = String::new();
+                        let mut mdate = None;
                         for attr in e.attributes().flatten() {
-                            if attr.key.as_ref() == b"key" {
-                                key = attr.decode_and_unescape_value(&reader)?.to_string();
+                            match attr.key.as_ref() {
+                                b"key" => key = attr.decode_and_unescape_value(&reader)?.to_string(),
+                                b"mdate" => mdate = Some(attr.decode_and_unescape_value(&reader)?.to_string()),
+                                _ => {}
                             }
                         }
+                        if tag_name == CORR_ENTRY_TAG && (!enable_insert || !key.starts_with(CORR_KEY_PREFIX)) {
+                            buf.clear();
+                            continue;
+                        }
+                        if tag_name == PROCEEDINGS_ENTRY_TAG && (!enable_insert || !venues::is_indexed_key(&key)) {
+                            buf.clear();
+                            continue;
+                        }
                         current_paper = Some(Paper {
                             title: String::new(),
+                            title_html: String::new(),
                             year: 0,
                             authors: Vec::new(),
                             venue: String::new(),
                             dblp_key: key,
                             ee_links: Vec::new(),
+                            bib: bib::Bib { mdate, ..Default::default() },
+                            paper_type: PaperType::Full,
                             citation_count: None,
                             abstract_text: None,
+                            open_access_url: None,
+                            references: None,
+                            match_method: None,
+                            match_confidence: None,
+                            provenance: Provenance::default(),
                         });
                         current_tag = tag_name;
                     }
@@ -258,6 +335,21 @@ async fn parse_and_insert(pool: &Pool<Postgres>, path: &str, args: Vec<String>)
                     "ee" => {
                         current_tag = "ee".to_string();
                     }
+                    "note" => {
+                        if let Some(ref mut p) = current_paper {
+                            p.bib.start_note();
+                            current_tag = tag_name;
+                        }
+                    }
+                    "title" => {
+                        if let Some(ref mut p) = current_paper {
+                            if let Err(e) = read_title(&mut reader, p) {
+                                eprintln!("XML parse error in <title> of {}: {} (stopping)", p.dblp_key, e);
+                                truncated = true;
+                                break;
+                            }
+                        }
+                    }
                     t if skip_tags.contains(&t) => {}
                     _ => {
                         if current_paper.is_some() {
@@ -277,34 +369,65 @@ async fn parse_and_insert(pool: &Pool<Postgres>, path: &str, args: Vec<String>)
                                 last.push_str(&text);
                             }
                         }
-                        "title" => paper.title.push_str(&text),
                         "year" => year_str.push_str(&text),
                         "booktitle" | "journal" => paper.venue.push_str(&text),
                         "ee" => paper.ee_links.push(text),
-                        _ => {}
+                        tag => paper.bib.push_text(tag, &text),
                     }
                 }
             }
             Ok(Event::End(ref e)) => {
                 let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                if tag_name == "dblp" {
+                    root_closed = true;
+                }
                 if tag_name.as_str() == TARGET_ENTRY_TAG {
                     if let Some(mut paper) = current_paper.take() {
-                        paper.year = year_str.parse().unwrap_or(0);
+                        paper.year = year_str.trim().parse().unwrap_or(0);
                         year_str.clear();
-                        if let Some(canonical) = get_canonical(&paper) {
-                            paper.venue = canonical;
+                        tidy_fields(&mut paper);
+                        paper.paper_type = classify::classify(&paper.title, &paper.venue, &paper.bib);
+                        if let Some(canonical) = venues::canonical_venue(&paper.dblp_key, &paper.venue, &paper.title) {
+                            paper.venue = canonical.to_string();
                             batch.push(paper);
                             if batch.len() >= 1000 {
                                 tx.send(std::mem::replace(&mut batch, Vec::with_capacity(1000))).await.context("Failed to push batch")?;
                             }
                         }
                     }
+                } else if tag_name.as_str() == PROCEEDINGS_ENTRY_TAG {
+                    if let Some(mut paper) = current_paper.take() {
+                        paper.year = year_str.trim().parse().unwrap_or(0);
+                        year_str.clear();
+                        tidy_fields(&mut paper);
+                        if let Some(canonical) = venues::canonical_venue(&paper.dblp_key, &paper.venue, &paper.title) {
+                            paper.venue = canonical.to_string();
+                            editions.push(paper);
+                        }
+                    }
+                } else if tag_name.as_str() == CORR_ENTRY_TAG {
+                    if let Some(mut paper) = current_paper.take() {
+                        paper.year = year_str.trim().parse().unwrap_or(0);
+                        year_str.clear();
+                        tidy_fields(&mut paper);
+                        corr_batch.push(paper);
+                        if corr_batch.len() >= 5000 {
+                            insert_corr_batch(pool, &corr_batch).await?;
+                            corr_batch.clear();
+                        }
+                    }
                 }
                 if tag_name == current_tag {
                     current_tag.clear();
                 }
             }
-            Ok(Event::Eof) => break,
+            Ok(Event::Eof) => {
+                if !root_closed {
+                    eprintln!("Dump ends before </dblp>; it looks truncated");
+                    truncated = true;
+                }
+                break;
+            }
             Err(e) => {
                 // quick-xml can keep returning the same Err at this position, so
                 // stop rather than silently dropping data or spinning forever.
@@ -313,6 +436,7 @@ async fn parse_and_insert(pool: &Pool<Postgres>, path: &str, args: Vec<String>)
                     reader.buffer_position(),
                     e
                 );
+                truncated = true;
                 break;
             }
             _ => {}
@@ -322,35 +446,221 @@ async fn parse_and_insert(pool: &Pool<Postgres>, path: &str, args: Vec<String>)
     if !batch.is_empty() {
         tx.send(batch).await.context("Failed to push final batch")?;
     }
+    insert_corr_batch(pool, &corr_batch).await?;
     
     // Close the pipeline: dropping tx ends stage 1, whose enriched_tx then drops
     // and ends stage 2.
     drop(tx);
 
     fetcher_handle.await.context("Fetcher task panicked")?;
-    inserter_handle.await.context("Inserter task panicked")?;
+    let diff = inserter_handle.await.context("Inserter task panicked")?;
+
+    if enable_insert {
+        proceedings::insert_editions(pool, &editions).await?;
+        println!("\nImported {} proceedings volumes", editions.len());
+        let linked = link_corr_preprints(pool).await?;
+        println!("Linked {} papers to their arXiv preprints", linked);
+    }
+    if let Some(diff) = diff {
+        if truncated {
+            eprintln!("Dump is incomplete; skipping the diff report and removal of missing papers");
+        } else {
+            diff.finish(pool, std::path::Path::new(&diff_out), prune_missing).await?;
+        }
+    }
 
     Ok(())
 }
 
-fn get_canonical(paper: &Paper) -> Option<String> {
-    if !paper.dblp_key.starts_with("conf/") {
-        return None;
-    }
-    let parts: Vec<&str> = paper.dblp_key.split('/').collect();
-    if parts.len() < 2 {
-        return None;
+/// Reader over a DBLP dump. Text is not trimmed: in "Beta <i>k</i>-means" the
+/// space before <i> is part of the title. Fields are trimmed when the entry is
+/// complete instead (`tidy_fields`).
+fn dump_reader<R: std::io::BufRead>(inner: R) -> Reader<R> {
+    let mut reader = Reader::from_reader(inner);
+    reader.trim_text(false);
+    reader
+}
+
+/// Read the rest of a `<title>` element, just after its start tag, into
+/// `title` and `title_html`.
+fn read_title<R: std::io::BufRead>(reader: &mut Reader<R>, paper: &mut Paper) -> Result<()> {
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) | Event::Empty(e) => {
+                title_markup(paper, &String::from_utf8_lossy(e.name().as_ref()), true);
+            }
+            Event::End(e) if e.name().as_ref() == b"title" => return Ok(()),
+            Event::End(e) => title_markup(paper, &String::from_utf8_lossy(e.name().as_ref()), false),
+            Event::Text(e) => {
+                let text = html_escape::decode_html_entities(&String::from_utf8_lossy(e.as_ref())).to_string();
+                paper.title.push_str(&text);
+                paper.title_html.push_str(&html_escape::encode_text(&text));
+            }
+            Event::Eof => anyhow::bail!("dump ends inside <title>"),
+            _ => {}
+        }
+        buf.clear();
     }
-    let short_name = parts[1].to_lowercase();
-    if let Some(canonical) = VENUE_MAP.get(&short_name) {
-        if paper.venue.to_lowercase().contains("workshop")
-            || paper.title.to_lowercase().contains("workshop")
-        {
-            return None;
+}
+
+/// Mirror an inline tag inside `<title>` into `title_html`. Only tags we
+/// emit ourselves reach the HTML (text is escaped separately), so the result
+/// is safe to render; `<br>` becomes a space in both forms.
+fn title_markup(paper: &mut Paper, tag: &str, open: bool) {
+    let html_tag = match tag {
+        "i" => "i",
+        "sub" => "sub",
+        "sup" => "sup",
+        "tt" => "code",
+        "br" => {
+            paper.title.push(' ');
+            paper.title_html.push(' ');
+            return;
         }
-        return Some(canonical.clone());
+        _ => return,
+    };
+    paper.title_html.push_str(if open { "<" } else { "</" });
+    paper.title_html.push_str(html_tag);
+    paper.title_html.push('>');
+}
+
+/// The reader doesn't trim text (see `parse_and_insert`), so collapse the
+/// whitespace DBLP's line breaks leave in multi-line fields.
+fn tidy_fields(paper: &mut Paper) {
+    let collapse = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+    paper.title = collapse(&paper.title);
+    paper.title_html = collapse(&paper.title_html);
+    paper.venue = collapse(&paper.venue);
+    for author in paper.authors.iter_mut() {
+        *author = collapse(author);
+    }
+    for link in paper.ee_links.iter_mut() {
+        *link = link.trim().to_string();
+    }
+    paper.bib.tidy();
+}
+
+/// Remember CoRR preprints (by arXiv id) so they can be matched to papers.
+async fn insert_corr_batch(pool: &Pool<Postgres>, batch: &[Paper]) -> Result<()> {
+    let mut keys = Vec::with_capacity(batch.len());
+    let mut arxiv_ids = Vec::with_capacity(batch.len());
+    let mut titles = Vec::with_capacity(batch.len());
+    let mut first_authors = Vec::with_capacity(batch.len());
+    let mut years = Vec::with_capacity(batch.len());
+    for p in batch {
+        let Some(id) = p.ee_links.iter().find_map(|l| arxiv_id(l)) else {
+            continue;
+        };
+        keys.push(p.dblp_key.as_str());
+        arxiv_ids.push(id);
+        titles.push(p.title.as_str());
+        first_authors.push(p.authors.first().map(String::as_str));
+        years.push(p.year);
+    }
+    if keys.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query(
+        "INSERT INTO corr_preprints (dblp_key, arxiv_id, title, first_author, year) \
+         SELECT * FROM UNNEST($1::text[], $2::text[], $3::text[], $4::text[], $5::int[]) \
+         ON CONFLICT (dblp_key) DO UPDATE SET \
+         arxiv_id = EXCLUDED.arxiv_id, title = EXCLUDED.title, \
+         first_author = EXCLUDED.first_author, year = EXCLUDED.year"
+    )
+    .bind(&keys)
+    .bind(&arxiv_ids)
+    .bind(&titles)
+    .bind(&first_authors)
+    .bind(&years)
+    .execute(pool).await?;
+
+    Ok(())
+}
+
+/// Give papers without an arXiv id the id of the CoRR preprint with the same
+/// normalised title and first author, published at most two years earlier or
+/// one year later. Titles matching several preprints are skipped as ambiguous.
+/// Returns the number of papers linked.
+async fn link_corr_preprints(pool: &Pool<Postgres>) -> Result<u64> {
+    let mut tx = pool.begin().await?;
+    let linked = sqlx::query(
+        "WITH candidates AS ( \
+             SELECT p.id AS paper_id, min(c.arxiv_id) AS arxiv_id \
+             FROM papers p \
+             JOIN paper_authors pa ON pa.paper_id = p.id AND pa.author_order = 0 \
+             JOIN authors a ON a.id = pa.author_id \
+             JOIN corr_preprints c ON normalize_title(c.title) = normalize_title(p.title) \
+                                  AND c.first_author = a.name \
+                                  AND c.year BETWEEN p.year - 2 AND p.year + 1 \
+             WHERE p.arxiv_id IS NULL \
+             GROUP BY p.id \
+             HAVING count(DISTINCT c.arxiv_id) = 1) \
+         UPDATE papers SET arxiv_id = candidates.arxiv_id \
+         FROM candidates WHERE papers.id = candidates.paper_id"
+    )
+    .execute(&mut *tx).await?
+    .rows_affected();
+
+    // Surface the preprint as links too, so it counts as open access.
+    sqlx::query(
+        "INSERT INTO paper_links (paper_id, url, kind, source) \
+         SELECT id, 'https://arxiv.org/abs/' || arxiv_id, 'arxiv', 'corr' FROM papers \
+         WHERE arxiv_id IS NOT NULL \
+           AND NOT EXISTS (SELECT 1 FROM paper_links l WHERE l.paper_id = papers.id AND l.kind = 'arxiv') \
+         UNION ALL \
+         SELECT id, 'https://arxiv.org/pdf/' || arxiv_id, 'pdf', 'corr' FROM papers WHERE arxiv_id IS NOT NULL \
+         ON CONFLICT DO NOTHING"
+    )
+    .execute(&mut *tx).await?;
+    tx.commit().await?;
+
+    Ok(linked)
+}
+
+/// The arXiv identifier in an arxiv.org abs/pdf URL, e.g. "2101.00001" or
+/// "cs/0112017" (old-style), without any version suffix.
+fn arxiv_id(url: &str) -> Option<String> {
+    let low = url.to_lowercase();
+    let pos = low.find("arxiv.org/")?;
+    let path = &low[pos + "arxiv.org/".len()..];
+    let id = path
+        .strip_prefix("abs/")
+        .or_else(|| path.strip_prefix("pdf/"))?
+        .trim_end_matches(".pdf");
+    let id = match id.rfind('v') {
+        Some(v) if v > 0 && id[v + 1..].chars().all(|c| c.is_ascii_digit()) && v + 1 < id.len() => &id[..v],
+        _ => id,
+    };
+    if id.is_empty() {
+        None
+    } else {
+        Some(id.to_string())
+    }
+}
+
+/// Classify an `<ee>` URL so clients can pick the kind of link they want.
+fn link_kind(url: &str) -> &'static str {
+    let low = url.to_lowercase();
+    let host = low
+        .split("://")
+        .nth(1)
+        .unwrap_or(&low)
+        .split('/')
+        .next()
+        .unwrap_or("");
+    match host.trim_start_matches("www.") {
+        "doi.org" | "dx.doi.org" => "doi",
+        "arxiv.org" => "arxiv",
+        "openreview.net" => "openreview",
+        "dl.acm.org" => "acm",
+        "ieeexplore.ieee.org" | "doi.ieeecomputersociety.org" => "ieee",
+        "link.springer.com" => "springer",
+        "usenix.org" => "usenix",
+        "proceedings.neurips.cc" | "papers.nips.cc" | "proceedings.mlr.press" | "aclanthology.org" => "proceedings",
+        _ => "other",
     }
-    None
 }
 
 fn extract_doi(url: &str) -> Option<String> {
@@ -396,83 +706,39 @@ fn extract_doi(url: &str) -> Option<String> {
     }
 }
 
-/// POST a batch of Semantic Scholar ids (e.g. "DOI:10.1145/...") and return the
-/// papers positionally aligned with `ids` (None for unknown / failed lookups).
-async fn semantic_scholar_batch(
-    client: &reqwest::Client,
-    api_key: Option<&str>,
-    ids: &[String],
-    fields: &str,
-) -> Vec<Option<SemanticScholarPaper>> {
-    let url = "https://api.semanticscholar.org/graph/v1/paper/batch";
-    let body = serde_json::json!({ "ids": ids });
-
-    for attempt in 0..3 {
-        let mut req = client
-            .post(url)
-            .query(&[("fields", fields)])
-            .header("User-Agent", "CSCS/1.0")
-            .json(&body);
-        if let Some(key) = api_key {
-            req = req.header("x-api-key", key);
-        }
-
-        match req.send().await {
-            Ok(resp) if resp.status().is_success() => {
-                match resp.json::<Vec<Option<SemanticScholarPaper>>>().await {
-                    Ok(parsed) => return parsed,
-                    Err(e) => {
-                        eprintln!("Semantic Scholar JSON parse error ({}): {}", fields, e);
-                        return Vec::new();
-                    }
-                }
-            }
-            Ok(resp)
-                if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
-                    || resp.status().is_server_error() =>
-            {
-                let delay_ms = 500 * (1_u64 << attempt);
-                tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
-            }
-            Ok(resp) => {
-                eprintln!("Semantic Scholar request failed with status {}", resp.status());
-                return Vec::new();
-            }
-            Err(err) => {
-                if attempt == 2 {
-                    eprintln!("Semantic Scholar request error: {}", err);
-                } else {
-                    let delay_ms = 500 * (1_u64 << attempt);
-                    tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
-                }
-            }
-        }
-    }
-    Vec::new()
-}
-
-/// Enrich a batch in place with citation counts and/or abstracts. Sequential and
-/// gated by the shared `pacer`, so the whole run honors Semantic Scholar's ~1
-/// request/second limit. When both are wanted they are requested together in a
-/// single call, halving the number of requests versus two separate passes.
+/// Enrich a batch in place with citation counts (plus reference edges) and/or
+/// abstracts. Providers are queried concurrently, each honoring its own rate
+/// limit, and their answers merged with `providers::merge`. When both fields
+/// are wanted they are requested together in a single call per provider.
+///
+/// Papers with a DOI are looked up by it. With `title_threshold` set, the rest
+/// are searched by title and accepted when `title_match::best_match` scores a
+/// candidate at or above the threshold.
+///
+/// With `max_age_days` set, papers whose wanted fields were all fetched more
+/// recently than that are left alone.
+///
+/// Returns the batch indices of papers that were looked up but got no answer
+/// from any provider. Providers report rate limits, server errors and "not
+/// found" alike as no answer, so callers that can retry later should.
 async fn enrich_batch(
     pool: &Pool<Postgres>,
-    client: &reqwest::Client,
-    api_key: Option<&str>,
-    batch: &mut Vec<Paper>,
+    providers: &[Box<dyn EnrichmentProvider>],
+    batch: &mut [Paper],
     want_cite: bool,
     want_abstract: bool,
-    pacer: &mut tokio::time::Interval,
-) -> Result<()> {
+    title_threshold: Option<f64>,
+    max_age_days: Option<i32>,
+) -> Result<Vec<usize>> {
     if batch.is_empty() || (!want_cite && !want_abstract) {
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     // For pure abstract backfill, skip papers that already have one in the DB.
     // When citations are wanted we must query every paper anyway (counts drift
     // over time), so the skip set only applies to abstract-only runs.
-    let skip: std::collections::HashSet<String> = if want_abstract && !want_cite {
-        let keys: Vec<String> = batch.iter().map(|p| p.dblp_key.clone()).collect();
+    let keys: Vec<String> = batch.iter().map(|p| p.dblp_key.clone()).collect();
+    let mut skip: std::collections::HashSet<String> = if want_abstract && !want_cite {
         let rows: Vec<(String,)> = sqlx::query_as(
             "SELECT dblp_key FROM papers WHERE dblp_key = ANY($1) AND abstract IS NOT NULL"
         )
@@ -482,60 +748,145 @@ async fn enrich_batch(
     } else {
         std::collections::HashSet::new()
     };
-
-    let mut fields: Vec<&str> = Vec::new();
-    if want_cite {
-        fields.push("citationCount");
-    }
-    if want_abstract {
-        fields.push("abstract");
+    if let Some(days) = max_age_days {
+        let mut fields: Vec<&str> = Vec::new();
+        if want_cite {
+            fields.push("citation_count");
+        }
+        if want_abstract {
+            fields.push("abstract");
+        }
+        let fresh: Vec<(String,)> = sqlx::query_as(
+            "SELECT p.dblp_key FROM papers p \
+             JOIN paper_provenance pv ON pv.paper_id = p.id \
+             WHERE p.dblp_key = ANY($1) AND pv.field = ANY($2) \
+               AND pv.fetched_at > now() - make_interval(days => $3) \
+             GROUP BY p.dblp_key \
+             HAVING count(*) = cardinality($2)"
+        )
+            .bind(&keys)
+            .bind(&fields)
+            .bind(days)
+            .fetch_all(pool).await?;
+        skip.extend(fresh.into_iter().map(|r| r.0));
     }
-    let fields = fields.join(",");
 
-    // Map each target paper (by batch index) to its first resolvable DOI.
-    let mut targets: Vec<(usize, String)> = Vec::new();
+    // Split target papers (by batch index) into those with a resolvable DOI
+    // and, if title matching is on, those we can only find by title.
+    let mut doi_targets: Vec<usize> = Vec::new();
+    let mut doi_keys: Vec<LookupKey> = Vec::new();
+    let mut title_targets: Vec<usize> = Vec::new();
+    let mut title_keys: Vec<LookupKey> = Vec::new();
     for (i, p) in batch.iter().enumerate() {
         if skip.contains(&p.dblp_key) {
             continue;
         }
-        for link in &p.ee_links {
-            if let Some(doi) = extract_doi(link) {
-                targets.push((i, format!("DOI:{}", doi)));
-                break;
-            }
+        let key = LookupKey {
+            doi: p.ee_links.iter().find_map(|l| extract_doi(l)),
+            title: p.title.clone(),
+            year: p.year,
+            first_author: p.authors.first().cloned(),
+        };
+        if key.doi.is_some() {
+            doi_targets.push(i);
+            doi_keys.push(key);
+        } else if title_threshold.is_some() && !key.title.is_empty() {
+            title_targets.push(i);
+            title_keys.push(key);
         }
     }
-    if targets.is_empty() {
-        return Ok(());
+    if doi_targets.is_empty() && title_targets.is_empty() {
+        return Ok(Vec::new());
     }
 
-    // Semantic Scholar's batch endpoint accepts up to 500 ids per request.
-    for chunk in targets.chunks(500) {
-        let ids: Vec<String> = chunk.iter().map(|(_, id)| id.clone()).collect();
+    let want = Want {
+        citations: want_cite,
+        abstracts: want_abstract,
+    };
+    let threshold = title_threshold.unwrap_or(1.0);
+    let title_keys = &title_keys;
+    let (by_doi, by_title) = futures::future::join(
+        futures::future::join_all(providers.iter().map(|p| p.lookup(&doi_keys, want))),
+        // Title searches are one request per paper; each provider works through
+        // them sequentially behind its own rate limiter.
+        futures::future::join_all(providers.iter().map(|p| async move {
+            let mut found = Vec::with_capacity(title_keys.len());
+            for key in title_keys {
+                let candidates = p.search_title(key, want).await;
+                found.push(title_match::best_match(key, candidates, threshold));
+            }
+            found
+        })),
+    )
+    .await;
+
+    // Collect each paper's answers in provider priority order, then merge.
+    let mut answers: Vec<Vec<(&'static str, providers::Enrichment)>> = vec![Vec::new(); batch.len()];
+    // Confidence is 1 for DOI matches and the best title score otherwise.
+    let mut matched: Vec<Option<(&'static str, f64)>> = vec![None; batch.len()];
+    for (provider, results) in providers.iter().zip(by_doi) {
+        for (&idx, res) in doi_targets.iter().zip(results) {
+            if let Some(found) = res {
+                answers[idx].push((provider.name(), found));
+                matched[idx] = Some(("doi", 1.0));
+            }
+        }
+    }
+    for (provider, results) in providers.iter().zip(by_title) {
+        for (&idx, res) in title_targets.iter().zip(results) {
+            if let Some((found, score)) = res {
+                answers[idx].push((provider.name(), found));
+                let best = matched[idx].map_or(score, |(_, c)| c.max(score));
+                matched[idx] = Some(("title", best));
+            }
+        }
+    }
 
-        // Gate every HTTP call through the shared pacer to honor the global limit.
-        pacer.tick().await;
-        let results = semantic_scholar_batch(client, api_key, &ids, &fields).await;
+    let unanswered: Vec<usize> = doi_targets
+        .iter()
+        .chain(&title_targets)
+        .copied()
+        .filter(|&idx| matched[idx].is_none())
+        .collect();
 
-        for ((idx, _), res) in chunk.iter().zip(results) {
-            if let Some(paper) = res {
-                if want_cite {
-                    if let Some(count) = paper.citation_count {
-                        batch[*idx].citation_count = Some(count);
-                    }
-                }
-                if want_abstract {
-                    if let Some(abs) = paper.abstract_text {
-                        if !abs.is_empty() {
-                            batch[*idx].abstract_text = Some(abs);
-                        }
-                    }
-                }
+    let mut matched_by_title = 0;
+    for (idx, found) in answers.into_iter().enumerate() {
+        let Some((method, confidence)) = matched[idx] else {
+            continue;
+        };
+        let (merged, provenance) = providers::merge(found);
+        batch[idx].match_method = Some(method);
+        batch[idx].match_confidence = Some(confidence as f32);
+        if method == "title" {
+            matched_by_title += 1;
+        }
+        if want_cite {
+            if let Some(count) = merged.citation_count {
+                batch[idx].citation_count = Some(count);
+            }
+            batch[idx].provenance.citation_count = provenance.citation_count;
+            batch[idx].references = merged
+                .references
+                .map(|refs| refs.into_iter().filter(|key| venues::is_indexed_key(key)).collect());
+        }
+        if want_abstract {
+            if let Some(abs) = merged.abstract_text {
+                batch[idx].abstract_text = Some(abs);
             }
+            batch[idx].provenance.abstract_text = provenance.abstract_text;
         }
+        batch[idx].open_access_url = merged.open_access_url;
+        batch[idx].provenance.open_access_url = provenance.open_access_url;
+    }
+    if !title_targets.is_empty() {
+        println!(
+            "\nTitle matching: {}/{} papers without a DOI matched",
+            matched_by_title,
+            title_targets.len()
+        );
     }
 
-    Ok(())
+    Ok(unanswered)
 }
 
 async fn insert_batch(pool: &Pool<Postgres>, batch: &mut Vec<Paper>) -> Result<()> {
@@ -570,12 +921,29 @@ async fn insert_batch(pool: &Pool<Postgres>, batch: &mut Vec<Paper>) -> Result<(
     // 2. Prepare papers & authors data
     let capacity = batch.len();
     let mut titles = Vec::with_capacity(capacity);
+    let mut title_htmls = Vec::with_capacity(capacity);
     let mut years = Vec::with_capacity(capacity);
     let mut ee_links = Vec::with_capacity(capacity);
     let mut dblp_keys = Vec::with_capacity(capacity);
     let mut cit_counts: Vec<Option<i32>> = Vec::with_capacity(capacity);
     let mut abstracts = Vec::with_capacity(capacity);
     let mut venue_ids = Vec::with_capacity(capacity);
+    let mut match_methods: Vec<Option<&str>> = Vec::with_capacity(capacity);
+    let mut match_confidences: Vec<Option<f32>> = Vec::with_capacity(capacity);
+    let mut dois: Vec<Option<String>> = Vec::with_capacity(capacity);
+    let mut arxiv_ids: Vec<Option<String>> = Vec::with_capacity(capacity);
+    let mut pages: Vec<Option<String>> = Vec::with_capacity(capacity);
+    let mut page_counts: Vec<Option<i32>> = Vec::with_capacity(capacity);
+    let mut volumes: Vec<Option<String>> = Vec::with_capacity(capacity);
+    let mut numbers: Vec<Option<String>> = Vec::with_capacity(capacity);
+    let mut crossrefs: Vec<Option<String>> = Vec::with_capacity(capacity);
+    let mut notes: Vec<Option<String>> = Vec::with_capacity(capacity);
+    let mut mdates: Vec<Option<String>> = Vec::with_capacity(capacity);
+    let mut paper_types: Vec<&str> = Vec::with_capacity(capacity);
+
+    let mut link_dblp_keys = Vec::new();
+    let mut link_urls = Vec::new();
+    let mut link_kinds = Vec::new();
 
     let mut pa_dblp_keys = Vec::new();
     let mut pa_author_orders = Vec::new();
@@ -626,13 +994,37 @@ async fn insert_batch(pool: &Pool<Postgres>, batch: &mut Vec<Paper>) -> Result<(
         let v_id = venue_map.get(&paper.venue).copied().unwrap_or(0);
         let dblp_key = paper.dblp_key.clone();
 
+        dois.push(paper.ee_links.iter().find_map(|l| extract_doi(l)));
+        arxiv_ids.push(paper.ee_links.iter().find_map(|l| arxiv_id(l)));
+        for url in &paper.ee_links {
+            link_dblp_keys.push(dblp_key.clone());
+            link_urls.push(url.clone());
+            link_kinds.push(link_kind(url));
+            if let Some(id) = arxiv_id(url) {
+                link_dblp_keys.push(dblp_key.clone());
+                link_urls.push(format!("https://arxiv.org/pdf/{}", id));
+                link_kinds.push("pdf");
+            }
+        }
+
         titles.push(paper.title);
+        title_htmls.push(paper.title_html);
         years.push(paper.year);
         ee_links.push(ee_link);
         dblp_keys.push(dblp_key.clone());
         cit_counts.push(paper.citation_count);
         abstracts.push(paper.abstract_text.clone());
         venue_ids.push(v_id);
+        match_methods.push(paper.match_method);
+        match_confidences.push(paper.match_confidence);
+        page_counts.push(paper.bib.page_count());
+        pages.push(paper.bib.pages);
+        volumes.push(paper.bib.volume);
+        numbers.push(paper.bib.number);
+        crossrefs.push(paper.bib.crossref);
+        notes.push(paper.bib.note);
+        mdates.push(paper.bib.mdate);
+        paper_types.push(paper.paper_type.as_str());
 
         for (idx, name) in paper.authors.into_iter().enumerate() {
             unique_author_names_set.insert(name.clone());
@@ -644,17 +1036,32 @@ async fn insert_batch(pool: &Pool<Postgres>, batch: &mut Vec<Paper>) -> Result<(
 
     if !dblp_keys.is_empty() {
         sqlx::query(
-            "INSERT INTO papers (venue_id, title, year, ee_link, dblp_key, citation_count, abstract) \
-             SELECT * FROM UNNEST($1::int[], $2::text[], $3::int[], $4::text[], $5::text[], $6::int[], $7::text[]) \
+            "INSERT INTO papers (venue_id, title, year, ee_link, dblp_key, citation_count, abstract, match_method, match_confidence, doi, arxiv_id, title_html, \
+                                 pages, page_count, volume, number, crossref, note, mdate, paper_type) \
+             SELECT * FROM UNNEST($1::int[], $2::text[], $3::int[], $4::text[], $5::text[], $6::int[], $7::text[], $8::text[], $9::real[], $10::text[], $11::text[], $12::text[], \
+                                  $13::text[], $14::int[], $15::text[], $16::text[], $17::text[], $18::text[], $19::text[]::date[], $20::text[]) \
              ON CONFLICT (dblp_key) DO UPDATE SET \
              venue_id = EXCLUDED.venue_id, \
              title = EXCLUDED.title, \
+             title_html = EXCLUDED.title_html, \
+             pages = EXCLUDED.pages, \
+             page_count = EXCLUDED.page_count, \
+             volume = EXCLUDED.volume, \
+             number = EXCLUDED.number, \
+             crossref = EXCLUDED.crossref, \
+             note = EXCLUDED.note, \
+             mdate = EXCLUDED.mdate, \
+             paper_type = EXCLUDED.paper_type, \
              year = EXCLUDED.year, \
+             doi = EXCLUDED.doi, \
+             arxiv_id = COALESCE(EXCLUDED.arxiv_id, papers.arxiv_id), \
              citation_count = CASE \
                  WHEN EXCLUDED.citation_count > 0 THEN EXCLUDED.citation_count \
                  ELSE COALESCE(papers.citation_count, EXCLUDED.citation_count) \
              END, \
-             abstract = COALESCE(EXCLUDED.abstract, papers.abstract)"
+             abstract = COALESCE(EXCLUDED.abstract, papers.abstract), \
+             match_method = COALESCE(EXCLUDED.match_method, papers.match_method), \
+             match_confidence = COALESCE(EXCLUDED.match_confidence, papers.match_confidence)"
         )
         .bind(&venue_ids)
         .bind(&titles)
@@ -663,6 +1070,19 @@ async fn insert_batch(pool: &Pool<Postgres>, batch: &mut Vec<Paper>) -> Result<(
         .bind(&dblp_keys)
         .bind(&cit_counts)
         .bind(&abstracts)
+        .bind(&match_methods)
+        .bind(&match_confidences)
+        .bind(&dois)
+        .bind(&arxiv_ids)
+        .bind(&title_htmls)
+        .bind(&pages)
+        .bind(&page_counts)
+        .bind(&volumes)
+        .bind(&numbers)
+        .bind(&crossrefs)
+        .bind(&notes)
+        .bind(&mdates)
+        .bind(&paper_types)
         .execute(&mut *tx).await?;
     }
 
@@ -675,6 +1095,24 @@ async fn insert_batch(pool: &Pool<Postgres>, batch: &mut Vec<Paper>) -> Result<(
         paper_map.insert(r.1, r.0);
     }
 
+    // 3b. Electronic-edition links, replacing whatever the previous import had.
+    // Links found by enrichment providers have their own source and are kept.
+    let paper_ids: Vec<i32> = paper_map.values().copied().collect();
+    sqlx::query("DELETE FROM paper_links WHERE paper_id = ANY($1) AND source = 'dblp'")
+        .bind(&paper_ids)
+        .execute(&mut *tx).await?;
+    if !link_urls.is_empty() {
+        let link_paper_ids: Vec<i32> = link_dblp_keys.iter().map(|k| paper_map.get(k).copied().unwrap_or(0)).collect();
+        sqlx::query("INSERT INTO paper_links (paper_id, url, kind) \
+                     SELECT * FROM UNNEST($1::int[], $2::text[], $3::text[]) AS v(paper_id, url, kind) \
+                     WHERE v.paper_id <> 0 \
+                     ON CONFLICT DO NOTHING")
+            .bind(&link_paper_ids)
+            .bind(&link_urls)
+            .bind(&link_kinds)
+            .execute(&mut *tx).await?;
+    }
+
     // 4. Bulk insert authors
     let unique_authors: Vec<String> = unique_author_names_set.into_iter().collect();
     if !unique_authors.is_empty() {
@@ -692,7 +1130,12 @@ async fn insert_batch(pool: &Pool<Postgres>, batch: &mut Vec<Paper>) -> Result<(
         author_map.insert(r.1, r.0);
     }
 
-    // 5. Bulk insert paper_authors
+    // 5. Bulk insert paper_authors, replacing the previous import's list so
+    // removed or reordered authors don't linger.
+    sqlx::query("DELETE FROM paper_authors WHERE paper_id = ANY($1)")
+        .bind(&paper_ids)
+        .execute(&mut *tx).await?;
+
     let mut final_pa_paper_ids = Vec::with_capacity(pa_dblp_keys.len());
     let mut final_pa_author_ids = Vec::with_capacity(pa_dblp_keys.len());
     let mut final_pa_author_orders = Vec::with_capacity(pa_dblp_keys.len());
@@ -731,6 +1174,8 @@ async fn update_citations_batch(pool: &Pool<Postgres>, batch: &[Paper]) -> Resul
 
     let mut dblp_keys = Vec::with_capacity(batch.len());
     let mut cit_counts = Vec::with_capacity(batch.len());
+    let mut match_methods = Vec::with_capacity(batch.len());
+    let mut match_confidences = Vec::with_capacity(batch.len());
 
     let mut dblp_key_seen = std::collections::HashSet::new();
 
@@ -742,20 +1187,297 @@ async fn update_citations_batch(pool: &Pool<Postgres>, batch: &[Paper]) -> Resul
             if count > 0 {
                 dblp_keys.push(paper.dblp_key.clone());
                 cit_counts.push(count);
+                match_methods.push(paper.match_method);
+                match_confidences.push(paper.match_confidence);
             }
         }
     }
 
     if !dblp_keys.is_empty() {
         sqlx::query(
-            "UPDATE papers SET citation_count = v.citation_count \
-             FROM (SELECT * FROM UNNEST($1::text[], $2::int[])) AS v(dblp_key, citation_count) \
+            "UPDATE papers SET citation_count = v.citation_count, \
+             match_method = COALESCE(v.match_method, papers.match_method), \
+             match_confidence = COALESCE(v.match_confidence, papers.match_confidence) \
+             FROM (SELECT * FROM UNNEST($1::text[], $2::int[], $3::text[], $4::real[])) \
+             AS v(dblp_key, citation_count, match_method, match_confidence) \
              WHERE papers.dblp_key = v.dblp_key AND v.citation_count > 0"
         )
         .bind(&dblp_keys)
         .bind(&cit_counts)
+        .bind(&match_methods)
+        .bind(&match_confidences)
         .execute(pool).await?;
     }
 
     Ok(())
 }
+
+fn collect_citation_edges(batch: &[Paper]) -> Vec<(String, String)> {
+    batch
+        .iter()
+        .flat_map(|p| p.references.iter().flatten().map(|r| (p.dblp_key.clone(), r.clone())))
+        .collect()
+}
+
+/// Papers whose references were fetched, i.e. whose stored edges are stale.
+fn collect_citing(batch: &[Paper]) -> Vec<String> {
+    batch
+        .iter()
+        .filter(|p| p.references.is_some())
+        .map(|p| p.dblp_key.clone())
+        .collect()
+}
+
+/// One `paper_provenance` row: (dblp_key, field, provider, external id, match method).
+type ProvenanceRow = (String, &'static str, &'static str, Option<String>, Option<&'static str>);
+
+fn collect_provenance(batch: &[Paper]) -> Vec<ProvenanceRow> {
+    let mut rows = Vec::new();
+    let mut dblp_key_seen = std::collections::HashSet::new();
+    for p in batch {
+        if !dblp_key_seen.insert(p.dblp_key.as_str()) {
+            continue;
+        }
+        let fields = [
+            ("citation_count", &p.provenance.citation_count),
+            ("abstract", &p.provenance.abstract_text),
+        ];
+        for (field, source) in fields {
+            if let Some(source) = source {
+                rows.push((
+                    p.dblp_key.clone(),
+                    field,
+                    source.provider,
+                    source.external_id.clone(),
+                    p.match_method,
+                ));
+            }
+        }
+    }
+    rows
+}
+
+/// Record where each freshly fetched field came from, stamping it with now().
+async fn upsert_provenance(pool: &Pool<Postgres>, rows: &[ProvenanceRow]) -> Result<()> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let dblp_keys: Vec<&str> = rows.iter().map(|r| r.0.as_str()).collect();
+    let fields: Vec<&str> = rows.iter().map(|r| r.1).collect();
+    let providers: Vec<&str> = rows.iter().map(|r| r.2).collect();
+    let external_ids: Vec<Option<&str>> = rows.iter().map(|r| r.3.as_deref()).collect();
+    let match_methods: Vec<Option<&str>> = rows.iter().map(|r| r.4).collect();
+
+    sqlx::query(
+        "INSERT INTO paper_provenance (paper_id, field, provider, external_id, match_method, fetched_at) \
+         SELECT p.id, v.field, v.provider, v.external_id, v.match_method, now() \
+         FROM UNNEST($1::text[], $2::text[], $3::text[], $4::text[], $5::text[]) \
+              AS v(dblp_key, field, provider, external_id, match_method) \
+         JOIN papers p ON p.dblp_key = v.dblp_key \
+         ON CONFLICT (paper_id, field) DO UPDATE SET \
+         provider = EXCLUDED.provider, \
+         external_id = EXCLUDED.external_id, \
+         match_method = EXCLUDED.match_method, \
+         fetched_at = EXCLUDED.fetched_at"
+    )
+    .bind(&dblp_keys)
+    .bind(&fields)
+    .bind(&providers)
+    .bind(&external_ids)
+    .bind(&match_methods)
+    .execute(pool).await?;
+
+    Ok(())
+}
+
+/// (dblp_key, open-access PDF URL, provider that found it)
+fn collect_oa_links(batch: &[Paper]) -> Vec<(String, String, &'static str)> {
+    batch
+        .iter()
+        .filter_map(|p| {
+            let url = p.open_access_url.clone()?;
+            let source = p.provenance.open_access_url.as_ref()?;
+            Some((p.dblp_key.clone(), url, source.provider))
+        })
+        .collect()
+}
+
+/// Store open-access PDFs found by providers as `pdf` links, replacing any
+/// earlier provider-found PDF for the same paper.
+async fn upsert_oa_links(pool: &Pool<Postgres>, links: &[(String, String, &'static str)]) -> Result<()> {
+    if links.is_empty() {
+        return Ok(());
+    }
+
+    let dblp_keys: Vec<&str> = links.iter().map(|l| l.0.as_str()).collect();
+    let urls: Vec<&str> = links.iter().map(|l| l.1.as_str()).collect();
+    let sources: Vec<&str> = links.iter().map(|l| l.2).collect();
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "DELETE FROM paper_links l USING papers p \
+         WHERE l.paper_id = p.id AND p.dblp_key = ANY($1) AND l.kind = 'pdf' AND l.source <> 'dblp'"
+    )
+    .bind(&dblp_keys)
+    .execute(&mut *tx).await?;
+    sqlx::query(
+        "INSERT INTO paper_links (paper_id, url, kind, source) \
+         SELECT p.id, v.url, 'pdf', v.source \
+         FROM UNNEST($1::text[], $2::text[], $3::text[]) AS v(dblp_key, url, source) \
+         JOIN papers p ON p.dblp_key = v.dblp_key \
+         ON CONFLICT DO NOTHING"
+    )
+    .bind(&dblp_keys)
+    .bind(&urls)
+    .bind(&sources)
+    .execute(&mut *tx).await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Insert citation edges given as (citing, cited) DBLP keys, first dropping
+/// the stored edges of the `citing` papers so references a provider no longer
+/// reports disappear. Edges whose cited paper isn't in Postgres yet are
+/// returned so the caller can retry them (with no `citing`) once the rest of
+/// the dump has been inserted.
+async fn insert_citations(
+    pool: &Pool<Postgres>,
+    citing: &[String],
+    edges: &[(String, String)],
+) -> Result<Vec<(String, String)>> {
+    if citing.is_empty() && edges.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let cited_keys: Vec<String> = edges.iter().map(|e| e.1.clone()).collect();
+    let known: std::collections::HashSet<String> =
+        sqlx::query_as::<_, (String,)>("SELECT dblp_key FROM papers WHERE dblp_key = ANY($1)")
+            .bind(&cited_keys)
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|r| r.0)
+            .collect();
+
+    let (resolved, unresolved): (Vec<_>, Vec<_>) =
+        edges.iter().cloned().partition(|e| known.contains(&e.1));
+
+    let mut tx = pool.begin().await?;
+    if !citing.is_empty() {
+        sqlx::query(
+            "DELETE FROM citations WHERE citing_paper_id IN (SELECT id FROM papers WHERE dblp_key = ANY($1))"
+        )
+        .bind(citing)
+        .execute(&mut *tx).await?;
+    }
+    if !resolved.is_empty() {
+        let citing_keys: Vec<String> = resolved.iter().map(|e| e.0.clone()).collect();
+        let cited: Vec<String> = resolved.iter().map(|e| e.1.clone()).collect();
+        sqlx::query(
+            "INSERT INTO citations (citing_paper_id, cited_paper_id) \
+             SELECT c.id, d.id FROM UNNEST($1::text[], $2::text[]) AS e(citing, cited) \
+             JOIN papers c ON c.dblp_key = e.citing \
+             JOIN papers d ON d.dblp_key = e.cited \
+             WHERE c.id <> d.id \
+             ON CONFLICT DO NOTHING"
+        )
+        .bind(&citing_keys)
+        .bind(&cited)
+        .execute(&mut *tx).await?;
+    }
+    tx.commit().await?;
+
+    Ok(unresolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blank_paper() -> Paper {
+        Paper {
+            title: String::new(),
+            title_html: String::new(),
+            year: 0,
+            authors: Vec::new(),
+            venue: String::new(),
+            dblp_key: "conf/test/X20".into(),
+            ee_links: Vec::new(),
+            bib: bib::Bib::default(),
+            paper_type: PaperType::Full,
+            citation_count: None,
+            abstract_text: None,
+            open_access_url: None,
+            references: None,
+            match_method: None,
+            match_confidence: None,
+            provenance: Provenance::default(),
+        }
+    }
+
+    /// (title, title_html) of the first `<title>` in `xml`, tidied as on import.
+    fn title_of(xml: &str) -> (String, String) {
+        let mut reader = dump_reader(xml.as_bytes());
+        let mut paper = blank_paper();
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf).unwrap() {
+                Event::Start(e) if e.name().as_ref() == b"title" => read_title(&mut reader, &mut paper).unwrap(),
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        tidy_fields(&mut paper);
+        (paper.title, paper.title_html)
+    }
+
+    #[test]
+    fn inline_markup_keeps_surrounding_spaces() {
+        let (title, html) = title_of("<inproceedings><title>Beta <i>k</i>-means.</title></inproceedings>");
+        assert_eq!(title, "Beta k-means.");
+        assert_eq!(html, "Beta <i>k</i>-means.");
+    }
+
+    #[test]
+    fn nested_markup_is_mirrored() {
+        let (title, html) = title_of("<title>Bounds for <i>O</i>(<i>n<sup>2</sup></i>) <tt>sort</tt></title>");
+        assert_eq!(title, "Bounds for O(n2) sort");
+        assert_eq!(html, "Bounds for <i>O</i>(<i>n<sup>2</sup></i>) <code>sort</code>");
+    }
+
+    #[test]
+    fn title_text_is_escaped_and_unknown_tags_dropped() {
+        let (title, html) = title_of("<title>A &lt;b&gt; &amp; <span>C</span><br/>D</title>");
+        assert_eq!(title, "A <b> & C D");
+        assert_eq!(html, "A &lt;b&gt; &amp; C D");
+    }
+
+    #[test]
+    fn truncated_title_is_an_error() {
+        let mut reader = dump_reader("<title>Cut <i>off".as_bytes());
+        let mut buf = Vec::new();
+        reader.read_event_into(&mut buf).unwrap();
+        assert!(read_title(&mut reader, &mut blank_paper()).is_err());
+    }
+
+    #[test]
+    fn tidy_fields_collapses_line_breaks() {
+        let mut paper = blank_paper();
+        paper.title = "\n  Fast   Paths\n  for All. ".into();
+        paper.title_html = " Fast <i>Paths</i>\n for All.".into();
+        paper.venue = "USENIX\n ATC".into();
+        paper.authors = vec![" Ann\n  One ".into()];
+        paper.ee_links = vec!["\n https://doi.org/10.1/a \n".into()];
+
+        tidy_fields(&mut paper);
+
+        assert_eq!(paper.title, "Fast Paths for All.");
+        assert_eq!(paper.title_html, "Fast <i>Paths</i> for All.");
+        assert_eq!(paper.venue, "USENIX ATC");
+        assert_eq!(paper.authors, vec!["Ann One".to_string()]);
+        assert_eq!(paper.ee_links, vec!["https://doi.org/10.1/a".to_string()]);
+    }
+}
@@ -0,0 +1,70 @@
+//! A local stand-in for enrichment APIs. Replays a scripted sequence of
+//! responses (status + body), one per request, and records every request body
+//! so tests can assert on what the client sent and how often it retried.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{StatusCode, Uri},
+    Router,
+};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct MockState {
+    responses: Mutex<VecDeque<(StatusCode, String)>>,
+    requests: Mutex<Vec<(String, String)>>,
+}
+
+pub struct MockServer {
+    pub base_url: String,
+    state: Arc<MockState>,
+}
+
+impl MockServer {
+    /// Bind to an ephemeral port and serve `responses` in order. Once the script
+    /// runs out every further request gets a 500.
+    pub async fn start(responses: Vec<(u16, &str)>) -> Self {
+        let state = Arc::new(MockState {
+            responses: Mutex::new(
+                responses
+                    .into_iter()
+                    .map(|(code, body)| (StatusCode::from_u16(code).unwrap(), body.to_string()))
+                    .collect(),
+            ),
+            requests: Mutex::new(Vec::new()),
+        });
+
+        let app = Router::new().fallback(replay).with_state(state.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        Self {
+            base_url: format!("http://{}", addr),
+            state,
+        }
+    }
+
+    /// (path and query, body) of every request received so far.
+    pub fn requests(&self) -> Vec<(String, String)> {
+        self.state.requests.lock().unwrap().clone()
+    }
+}
+
+async fn replay(State(state): State<Arc<MockState>>, uri: Uri, body: Bytes) -> (StatusCode, String) {
+    state
+        .requests
+        .lock()
+        .unwrap()
+        .push((uri.to_string(), String::from_utf8_lossy(&body).into_owned()));
+    state
+        .responses
+        .lock()
+        .unwrap()
+        .pop_front()
+        .unwrap_or((StatusCode::INTERNAL_SERVER_ERROR, "script exhausted".to_string()))
+}
@@ -0,0 +1,128 @@
+//! DBLP `<proceedings>` records: one per edition of a venue ("OSDI 2022"),
+//! stored in `venue_editions` and referenced by papers' `<crossref>`.
+
+use anyhow::Result;
+use sqlx::{Pool, Postgres};
+use std::collections::HashMap;
+
+use crate::Paper;
+
+const MONTHS: &[&str] = &[
+    "january", "february", "march", "april", "may", "june", "july", "august",
+    "september", "october", "november", "december",
+];
+
+/// Longest comma-separated part taken as part of a location ("Virtual Event").
+const MAX_LOCATION_WORDS: usize = 4;
+
+/// Location from a proceedings title. DBLP doesn't record it separately, but
+/// titles end in "<city>, <region>, <country>, <dates>, <year>", e.g.
+/// "..., OSDI 2022, Carlsbad, CA, USA, July 11-13, 2022": the location is the
+/// run of short, number-free parts right before the dates.
+pub fn location(title: &str) -> Option<String> {
+    let parts: Vec<&str> = title.split(',').map(str::trim).collect();
+    let dates = parts.iter().rposition(|p| {
+        let lower = p.to_lowercase();
+        MONTHS.iter().any(|m| lower.starts_with(m))
+    })?;
+    // The first part is always the conference name.
+    let start = (1..dates)
+        .rev()
+        .take_while(|&i| {
+            let p = parts[i];
+            !p.is_empty()
+                && !p.chars().any(|c| c.is_ascii_digit())
+                && p.split_whitespace().count() <= MAX_LOCATION_WORDS
+        })
+        .last()?;
+    Some(parts[start..dates].join(", "))
+}
+
+/// Upsert editions; `batch` holds proceedings records already mapped to their
+/// canonical venue.
+pub async fn insert_editions(pool: &Pool<Postgres>, batch: &[Paper]) -> Result<()> {
+    if batch.is_empty() {
+        return Ok(());
+    }
+    let mut tx = pool.begin().await?;
+
+    let venue_names: Vec<&str> = batch.iter().map(|p| p.venue.as_str()).collect();
+    sqlx::query("INSERT INTO venues (raw_name) SELECT * FROM UNNEST($1::text[]) ON CONFLICT (raw_name) DO NOTHING")
+        .bind(&venue_names)
+        .execute(&mut *tx).await?;
+    let venue_ids: HashMap<String, i32> = sqlx::query_as::<_, (i32, String)>("SELECT id, raw_name FROM venues WHERE raw_name = ANY($1)")
+        .bind(&venue_names)
+        .fetch_all(&mut *tx).await?
+        .into_iter()
+        .map(|(id, name)| (name, id))
+        .collect();
+
+    let keys: Vec<&str> = batch.iter().map(|p| p.dblp_key.as_str()).collect();
+    let ids: Vec<i32> = batch.iter().map(|p| venue_ids.get(&p.venue).copied().unwrap_or(0)).collect();
+    let years: Vec<Option<i32>> = batch.iter().map(|p| (p.year > 0).then_some(p.year)).collect();
+    let titles: Vec<&str> = batch.iter().map(|p| p.title.as_str()).collect();
+    let locations: Vec<Option<String>> = batch.iter().map(|p| location(&p.title)).collect();
+    let publishers: Vec<Option<&str>> = batch.iter().map(|p| p.bib.publisher.as_deref()).collect();
+    let isbns: Vec<Option<&str>> = batch.iter().map(|p| p.bib.isbn.as_deref()).collect();
+
+    sqlx::query(
+        "INSERT INTO venue_editions (dblp_key, venue_id, year, title, location, publisher, isbn) \
+         SELECT * FROM UNNEST($1::text[], $2::int[], $3::int[], $4::text[], $5::text[], $6::text[], $7::text[]) \
+             AS e(dblp_key, venue_id, year, title, location, publisher, isbn) \
+         WHERE e.venue_id <> 0 \
+         ON CONFLICT (dblp_key) DO UPDATE SET \
+         venue_id = EXCLUDED.venue_id, \
+         year = EXCLUDED.year, \
+         title = EXCLUDED.title, \
+         location = EXCLUDED.location, \
+         publisher = EXCLUDED.publisher, \
+         isbn = EXCLUDED.isbn"
+    )
+    .bind(&keys)
+    .bind(&ids)
+    .bind(&years)
+    .bind(&titles)
+    .bind(&locations)
+    .bind(&publishers)
+    .bind(&isbns)
+    .execute(&mut *tx).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn location_between_acronym_and_dates() {
+        let title = "16th USENIX Symposium on Operating Systems Design and Implementation, \
+                     OSDI 2022, Carlsbad, CA, USA, July 11-13, 2022";
+        assert_eq!(location(title).as_deref(), Some("Carlsbad, CA, USA"));
+    }
+
+    #[test]
+    fn acm_style_title() {
+        let title = "Proceedings of the 29th Symposium on Operating Systems Principles, \
+                     SOSP 2023, Koblenz, Germany, October 23-26, 2023";
+        assert_eq!(location(title).as_deref(), Some("Koblenz, Germany"));
+    }
+
+    #[test]
+    fn virtual_events_and_missing_locations() {
+        let title = "SIGCOMM '21: ACM SIGCOMM 2021 Conference, Virtual Event, USA, August 23-27, 2021";
+        assert_eq!(location(title).as_deref(), Some("Virtual Event, USA"));
+        assert_eq!(location("Proceedings of FAST 2020"), None);
+    }
+
+    #[test]
+    fn long_conference_names_are_not_locations() {
+        let title = "Proceedings of the 2020 International Conference on Management of Data, \
+                     SIGMOD Conference 2020, online conference [Portland, OR, USA], June 14-19, 2020";
+        assert_eq!(location(title).as_deref(), Some("online conference [Portland, OR, USA]"));
+        let title = "Proceedings of the ACM SIGMOD International Conference on Management of Data, \
+                     Portland, OR, USA, June 14-19, 2020";
+        assert_eq!(location(title).as_deref(), Some("Portland, OR, USA"));
+    }
+}
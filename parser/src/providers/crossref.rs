@@ -0,0 +1,311 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tokio::time::Duration;
+
+use super::title_match::TitleCandidate;
+use super::{normalize_doi, send_with_retry, Enrichment, EnrichmentProvider, LookupKey, RateLimiter, Want};
+
+/// DOIs per `filter=doi:a,doi:b,...` request.
+const CHUNK: usize = 50;
+/// Candidates fetched per title search.
+const TITLE_CANDIDATES: usize = 5;
+
+#[derive(Deserialize, Debug)]
+struct CrossrefResponse {
+    message: CrossrefMessage,
+}
+
+#[derive(Deserialize, Debug)]
+struct CrossrefMessage {
+    items: Vec<CrossrefWork>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CrossrefWork {
+    #[serde(rename = "DOI")]
+    doi: String,
+    #[serde(rename = "is-referenced-by-count")]
+    referenced_by_count: Option<i32>,
+    #[serde(rename = "abstract")]
+    abstract_text: Option<String>,
+    #[serde(default)]
+    title: Vec<String>,
+    #[serde(default)]
+    author: Vec<CrossrefAuthor>,
+    issued: Option<CrossrefDate>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CrossrefAuthor {
+    given: Option<String>,
+    family: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CrossrefDate {
+    #[serde(rename = "date-parts")]
+    date_parts: Vec<Vec<Option<i32>>>,
+}
+
+impl CrossrefWork {
+    fn year(&self) -> Option<i32> {
+        self.issued.as_ref()?.date_parts.first()?.first().copied().flatten()
+    }
+
+    fn author_names(&self) -> Vec<String> {
+        self.author
+            .iter()
+            .filter_map(|a| match (&a.given, &a.family) {
+                (Some(given), Some(family)) => Some(format!("{} {}", given, family)),
+                (None, Some(family)) => Some(family.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Crossref has citation counts and (JATS) abstracts for most publisher DOIs,
+/// but no reliable open-access information, so it never sets that field.
+pub struct CrossrefClient {
+    http: reqwest::Client,
+    base_url: String,
+    mailto: Option<String>,
+    limiter: RateLimiter,
+}
+
+impl CrossrefClient {
+    pub fn new(base_url: String, mailto: Option<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url,
+            mailto,
+            limiter: RateLimiter::new(Duration::from_millis(200)),
+        }
+    }
+
+    async fn works_by_doi(&self, dois: &[String]) -> Vec<CrossrefWork> {
+        let filter = dois.iter().map(|d| format!("doi:{}", d)).collect::<Vec<_>>().join(",");
+        self.works(&[("filter", filter.as_str())], dois.len(), "DOI,is-referenced-by-count,abstract")
+            .await
+    }
+
+    /// GET `/works` with extra query parameters, returning up to `rows` works.
+    async fn works(&self, params: &[(&str, &str)], rows: usize, select: &str) -> Vec<CrossrefWork> {
+        let url = format!("{}/works", self.base_url.trim_end_matches('/'));
+        let rows = rows.to_string();
+
+        let resp = send_with_retry("Crossref", &self.limiter, || {
+            let mut req = self
+                .http
+                .get(&url)
+                .query(params)
+                .query(&[("rows", rows.as_str()), ("select", select)])
+                .header("User-Agent", "CSCS/1.0");
+            if let Some(ref mailto) = self.mailto {
+                req = req.query(&[("mailto", mailto)]);
+            }
+            req
+        })
+        .await;
+
+        let Some(resp) = resp else {
+            return Vec::new();
+        };
+        match resp.json::<CrossrefResponse>().await {
+            Ok(r) => r.message.items,
+            Err(e) => {
+                eprintln!("Crossref JSON parse error: {}", e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+fn to_enrichment(work: &CrossrefWork, want: Want) -> Enrichment {
+    Enrichment {
+        citation_count: work.referenced_by_count.filter(|_| want.citations),
+        abstract_text: work
+            .abstract_text
+            .as_deref()
+            .filter(|_| want.abstracts)
+            .map(strip_jats),
+        references: None,
+        open_access_url: None,
+        external_id: Some(normalize_doi(&work.doi)),
+    }
+}
+
+/// Crossref abstracts are JATS XML (`<jats:p>...</jats:p>`); keep only the text.
+fn strip_jats(raw: &str) -> String {
+    let mut text = String::with_capacity(raw.len());
+    let mut in_tag = false;
+    for c in raw.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                text.push(' ');
+            }
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[async_trait]
+impl EnrichmentProvider for CrossrefClient {
+    fn name(&self) -> &'static str {
+        "crossref"
+    }
+
+    async fn lookup(&self, keys: &[LookupKey], want: Want) -> Vec<Option<Enrichment>> {
+        let targets: Vec<(usize, String)> = keys
+            .iter()
+            .enumerate()
+            .filter_map(|(i, k)| Some((i, normalize_doi(k.doi.as_ref()?))))
+            // The filter has no escaping, so a DOI containing its separator
+            // would split into bogus ones; leave those papers unmatched.
+            .filter(|(_, doi)| !doi.contains(','))
+            .collect();
+
+        let mut out: Vec<Option<Enrichment>> = keys.iter().map(|_| None).collect();
+        for chunk in targets.chunks(CHUNK) {
+            let dois: Vec<String> = chunk.iter().map(|(_, d)| d.clone()).collect();
+
+            let by_doi: HashMap<String, CrossrefWork> = self
+                .works_by_doi(&dois)
+                .await
+                .into_iter()
+                .map(|w| (normalize_doi(&w.doi), w))
+                .collect();
+
+            for (idx, doi) in chunk {
+                if let Some(work) = by_doi.get(doi) {
+                    out[*idx] = Some(to_enrichment(work, want));
+                }
+            }
+        }
+        out
+    }
+
+    async fn search_title(&self, key: &LookupKey, want: Want) -> Vec<TitleCandidate> {
+        let mut params = vec![("query.bibliographic", key.title.clone())];
+        if let Some(ref author) = key.first_author {
+            params.push(("query.author", author.clone()));
+        }
+        if key.year > 0 {
+            params.push((
+                "filter",
+                format!("from-pub-date:{},until-pub-date:{}", key.year - 1, key.year + 1),
+            ));
+        }
+        let params: Vec<(&str, &str)> = params.iter().map(|(k, v)| (*k, v.as_str())).collect();
+
+        self.works(
+            &params,
+            TITLE_CANDIDATES,
+            "DOI,title,author,issued,is-referenced-by-count,abstract",
+        )
+        .await
+        .into_iter()
+        .map(|work| TitleCandidate {
+            title: work.title.first().cloned().unwrap_or_default(),
+            year: work.year(),
+            authors: work.author_names(),
+            enrichment: to_enrichment(&work, want),
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+
+    const WORKS: &str = r#"{"status": "ok", "message": {"items": [
+        {"DOI": "10.1/A", "is-referenced-by-count": 4,
+         "abstract": "<jats:title>Abstract</jats:title><jats:p>Fast   consensus.</jats:p>"}
+    ]}}"#;
+
+    #[tokio::test]
+    async fn lookup_strips_jats_and_matches_by_doi() {
+        let mock = MockServer::start(vec![(200, WORKS)]).await;
+        let client = CrossrefClient::new(mock.base_url.clone(), None);
+        let keys = vec![
+            LookupKey { doi: Some("https://doi.org/10.1/a".into()), ..Default::default() },
+            LookupKey { doi: Some("10.1/missing".into()), ..Default::default() },
+        ];
+
+        let res = client.lookup(&keys, Want { citations: true, abstracts: true }).await;
+
+        let hit = res[0].as_ref().unwrap();
+        assert_eq!(hit.citation_count, Some(4));
+        assert_eq!(hit.abstract_text.as_deref(), Some("Abstract Fast consensus."));
+        assert!(hit.open_access_url.is_none());
+        assert!(res[1].is_none());
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].0.contains("filter=doi%3A10.1%2Fa%2Cdoi%3A10.1%2Fmissing"));
+    }
+
+    #[tokio::test]
+    async fn lookup_skips_dois_containing_the_separator() {
+        let mock = MockServer::start(vec![(200, WORKS)]).await;
+        let client = CrossrefClient::new(mock.base_url.clone(), None);
+        let keys = vec![
+            LookupKey { doi: Some("10.1/a".into()), ..Default::default() },
+            LookupKey { doi: Some("10.1002/(sici)1097,4571".into()), ..Default::default() },
+        ];
+
+        let res = client.lookup(&keys, Want { citations: true, abstracts: false }).await;
+
+        assert_eq!(res[0].as_ref().unwrap().citation_count, Some(4));
+        assert!(res[1].is_none());
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].0.contains("filter=doi%3A10.1%2Fa&"));
+    }
+
+    #[tokio::test]
+    async fn lookup_gives_up_on_repeated_rate_limits() {
+        let mock = MockServer::start(vec![(429, ""), (429, ""), (429, "")]).await;
+        let client = CrossrefClient::new(mock.base_url.clone(), None);
+        let keys = vec![LookupKey { doi: Some("10.1/a".into()), ..Default::default() }];
+
+        let res = client.lookup(&keys, Want { citations: true, abstracts: false }).await;
+
+        assert!(res[0].is_none());
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn search_title_reads_title_authors_and_year() {
+        let mock = MockServer::start(vec![(200, r#"{"status": "ok", "message": {"items": [
+            {"DOI": "10.1/c", "title": ["Beta k-means"], "is-referenced-by-count": 3,
+             "author": [{"given": "Ann", "family": "One"}, {"name": "Some Consortium"}],
+             "issued": {"date-parts": [[2021, 6]]}}
+        ]}}"#)]).await;
+        let client = CrossrefClient::new(mock.base_url.clone(), None);
+        let key = LookupKey {
+            title: "Beta k-means.".into(),
+            year: 2021,
+            first_author: Some("Ann One".into()),
+            ..Default::default()
+        };
+
+        let found = client.search_title(&key, Want { citations: true, abstracts: false }).await;
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].title, "Beta k-means");
+        assert_eq!(found[0].year, Some(2021));
+        assert_eq!(found[0].authors, vec!["Ann One".to_string()]);
+        assert_eq!(found[0].enrichment.citation_count, Some(3));
+        let sent = &mock.requests()[0].0;
+        assert!(sent.contains("query.author=Ann+One"));
+        assert!(sent.contains("filter=from-pub-date%3A2020%2Cuntil-pub-date%3A2022"));
+    }
+}
@@ -0,0 +1,294 @@
+//! Enrichment providers: external bibliographic APIs that supply citation
+//! counts, abstracts, reference lists and open-access links for our papers.
+
+use async_trait::async_trait;
+use std::env;
+use tokio::sync::Mutex;
+use tokio::time::{Duration, Interval, MissedTickBehavior};
+
+pub mod crossref;
+pub mod openalex;
+pub mod semantic_scholar;
+pub mod title_match;
+
+use crossref::CrossrefClient;
+use openalex::OpenAlexClient;
+use semantic_scholar::SemanticScholarClient;
+use title_match::TitleCandidate;
+
+/// What we know about a paper when asking a provider for it.
+#[derive(Default)]
+pub struct LookupKey {
+    pub doi: Option<String>,
+    pub title: String,
+    pub year: i32,
+    pub first_author: Option<String>,
+}
+
+/// Which fields the caller needs; providers skip the rest where the API allows.
+#[derive(Clone, Copy)]
+pub struct Want {
+    pub citations: bool,
+    pub abstracts: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Enrichment {
+    pub citation_count: Option<i32>,
+    pub abstract_text: Option<String>,
+    /// DBLP keys of referenced works; `None` when the provider doesn't report
+    /// them. Only Semantic Scholar exposes DBLP ids for references, so the
+    /// other providers always leave this `None`.
+    pub references: Option<Vec<String>>,
+    pub open_access_url: Option<String>,
+    /// The provider's own id for the work (S2 paper id, OpenAlex work id, or
+    /// the DOI for Crossref), recorded as provenance.
+    pub external_id: Option<String>,
+}
+
+/// Which provider supplied a merged field, and under which id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Source {
+    pub provider: &'static str,
+    pub external_id: Option<String>,
+}
+
+/// Per-field sources of a merged `Enrichment`; None where no provider had it.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Provenance {
+    pub citation_count: Option<Source>,
+    pub abstract_text: Option<Source>,
+    pub open_access_url: Option<Source>,
+}
+
+#[async_trait]
+pub trait EnrichmentProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Look up `keys`, returning results positionally aligned with them (None
+    /// for papers the provider doesn't know or that failed to fetch).
+    async fn lookup(&self, keys: &[LookupKey], want: Want) -> Vec<Option<Enrichment>>;
+
+    /// Search by title (plus year / first author where the API supports it) for
+    /// a paper with no DOI. Returns the provider's top candidates unscored;
+    /// `title_match::best_match` decides whether any of them is the paper.
+    async fn search_title(&self, key: &LookupKey, want: Want) -> Vec<TitleCandidate>;
+}
+
+/// Minimum spacing between requests to one provider. Each provider owns its
+/// own limiter, so a slow API never eats into another's budget.
+pub struct RateLimiter {
+    interval: Mutex<Interval>,
+}
+
+impl RateLimiter {
+    pub fn new(period: Duration) -> Self {
+        // Delay keeps >=1 period between ticks even after a slow request/retry,
+        // so it never bursts.
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self {
+            interval: Mutex::new(interval),
+        }
+    }
+
+    pub async fn wait(&self) {
+        self.interval.lock().await.tick().await;
+    }
+}
+
+/// Attempts per request, including the first.
+const ATTEMPTS: u32 = 3;
+
+/// Send a request, retrying 429s, 5xx and transport errors with exponential
+/// backoff. Every attempt, retries included, first waits for the provider's
+/// `limiter`, so a 429 is never retried faster than the limit allows. Returns
+/// None (after logging) when the request ultimately fails.
+pub async fn send_with_retry<F>(provider: &str, limiter: &RateLimiter, build: F) -> Option<reqwest::Response>
+where
+    F: Fn() -> reqwest::RequestBuilder,
+{
+    for attempt in 0..ATTEMPTS {
+        let last = attempt + 1 == ATTEMPTS;
+        limiter.wait().await;
+        match build().send().await {
+            Ok(resp) if resp.status().is_success() => return Some(resp),
+            // "No such paper" is an expected answer for lookups, not an error.
+            Ok(resp) if resp.status() == reqwest::StatusCode::NOT_FOUND => return None,
+            Ok(resp)
+                if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    || resp.status().is_server_error() =>
+            {
+                if last {
+                    eprintln!("{} request failed with status {} after {} attempts", provider, resp.status(), ATTEMPTS);
+                }
+            }
+            Ok(resp) => {
+                eprintln!("{} request failed with status {}", provider, resp.status());
+                return None;
+            }
+            Err(err) => {
+                if last {
+                    eprintln!("{} request error: {}", provider, err);
+                }
+            }
+        }
+        if !last {
+            tokio::time::sleep(Duration::from_millis(500 * (1_u64 << attempt))).await;
+        }
+    }
+    None
+}
+
+/// Combine answers from several providers, given in priority order as
+/// `(provider name, answer)`, and report which provider each field came from.
+///
+/// - citation count: the highest reported, since providers differ mainly in
+///   coverage and undercount rather than overcount;
+/// - abstract and open-access URL: the first provider that has one;
+/// - references: the union, in first-seen order.
+pub fn merge(results: Vec<(&'static str, Enrichment)>) -> (Enrichment, Provenance) {
+    let mut merged = Enrichment::default();
+    let mut provenance = Provenance::default();
+    for (provider, r) in results {
+        let source = Source {
+            provider,
+            external_id: r.external_id.clone(),
+        };
+        if let Some(count) = r.citation_count {
+            if merged.citation_count.is_none_or(|c| count > c) {
+                merged.citation_count = Some(count);
+                provenance.citation_count = Some(source.clone());
+            }
+        }
+        if merged.abstract_text.is_none() {
+            merged.abstract_text = r.abstract_text.filter(|a| !a.is_empty());
+            if merged.abstract_text.is_some() {
+                provenance.abstract_text = Some(source.clone());
+            }
+        }
+        if merged.open_access_url.is_none() {
+            merged.open_access_url = r.open_access_url;
+            if merged.open_access_url.is_some() {
+                provenance.open_access_url = Some(source);
+            }
+        }
+        if let Some(references) = r.references {
+            let merged = merged.references.get_or_insert_with(Vec::new);
+            for reference in references {
+                if !merged.contains(&reference) {
+                    merged.push(reference);
+                }
+            }
+        }
+    }
+    (merged, provenance)
+}
+
+/// Build the providers named in `list` (comma separated, priority order), e.g.
+/// "semantic_scholar,openalex,crossref". Base URLs come from the environment so
+/// each can be pointed at a local mock; `s2_url` overrides Semantic Scholar's.
+pub fn from_names(list: &str, s2_url: Option<String>) -> anyhow::Result<Vec<Box<dyn EnrichmentProvider>>> {
+    let mailto = env::var("ENRICH_MAILTO").ok().filter(|m| !m.is_empty());
+    let mut providers: Vec<Box<dyn EnrichmentProvider>> = Vec::new();
+
+    for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        match name {
+            "semantic_scholar" => {
+                let api_key = env::var("SEMANTIC_SCHOLAR_API_KEY").ok();
+                if api_key.is_none() {
+                    eprintln!("Warning: SEMANTIC_SCHOLAR_API_KEY not set; requests will be heavily rate-limited.");
+                }
+                let base_url = s2_url
+                    .clone()
+                    .or_else(|| env::var("SEMANTIC_SCHOLAR_API_URL").ok())
+                    .unwrap_or_else(|| "https://api.semanticscholar.org/graph/v1".into());
+                providers.push(Box::new(SemanticScholarClient::new(api_key, base_url)));
+            }
+            "openalex" => {
+                let base_url = env::var("OPENALEX_API_URL")
+                    .unwrap_or_else(|_| "https://api.openalex.org".into());
+                providers.push(Box::new(OpenAlexClient::new(base_url, mailto.clone())));
+            }
+            "crossref" => {
+                let base_url = env::var("CROSSREF_API_URL")
+                    .unwrap_or_else(|_| "https://api.crossref.org".into());
+                providers.push(Box::new(CrossrefClient::new(base_url, mailto.clone())));
+            }
+            other => anyhow::bail!("Unknown enrichment provider: {}", other),
+        }
+    }
+    Ok(providers)
+}
+
+/// Strip a DOI down to its lowercase `10.xxxx/...` form so answers can be
+/// matched back to the request regardless of URL prefix or case.
+pub fn normalize_doi(doi: &str) -> String {
+    let lowered = doi.trim().to_lowercase();
+    lowered
+        .strip_prefix("https://doi.org/")
+        .or_else(|| lowered.strip_prefix("http://doi.org/"))
+        .unwrap_or(&lowered)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_prefers_highest_count_and_first_abstract() {
+        let s2 = Enrichment {
+            citation_count: Some(10),
+            abstract_text: Some("from s2".into()),
+            references: Some(vec!["conf/osdi/A".into()]),
+            open_access_url: None,
+            external_id: Some("s2id".into()),
+        };
+        let openalex = Enrichment {
+            citation_count: Some(25),
+            abstract_text: Some("from openalex".into()),
+            references: None,
+            open_access_url: Some("https://arxiv.org/pdf/1".into()),
+            external_id: Some("W1".into()),
+        };
+        let crossref = Enrichment {
+            citation_count: None,
+            abstract_text: None,
+            references: Some(vec!["conf/osdi/A".into(), "conf/sosp/B".into()]),
+            open_access_url: Some("https://example.org/pdf".into()),
+            external_id: Some("10.1/a".into()),
+        };
+
+        let (merged, provenance) =
+            merge(vec![("semantic_scholar", s2), ("openalex", openalex), ("crossref", crossref)]);
+
+        assert_eq!(merged.citation_count, Some(25));
+        assert_eq!(merged.abstract_text.as_deref(), Some("from s2"));
+        assert_eq!(merged.open_access_url.as_deref(), Some("https://arxiv.org/pdf/1"));
+        assert_eq!(merged.references, Some(vec!["conf/osdi/A".to_string(), "conf/sosp/B".to_string()]));
+        assert_eq!(
+            provenance.citation_count,
+            Some(Source { provider: "openalex", external_id: Some("W1".into()) })
+        );
+        assert_eq!(provenance.abstract_text.map(|s| s.provider), Some("semantic_scholar"));
+        assert_eq!(provenance.open_access_url.map(|s| s.provider), Some("openalex"));
+    }
+
+    #[test]
+    fn merge_skips_empty_abstracts() {
+        let (merged, provenance) = merge(vec![
+            ("semantic_scholar", Enrichment { abstract_text: Some(String::new()), ..Default::default() }),
+            ("openalex", Enrichment { abstract_text: Some("real".into()), ..Default::default() }),
+        ]);
+        assert_eq!(merged.abstract_text.as_deref(), Some("real"));
+        assert_eq!(provenance.abstract_text.map(|s| s.provider), Some("openalex"));
+        assert!(provenance.citation_count.is_none());
+    }
+
+    #[test]
+    fn normalize_doi_strips_prefix_and_case() {
+        assert_eq!(normalize_doi("https://doi.org/10.1145/ABC"), "10.1145/abc");
+        assert_eq!(normalize_doi("10.1145/abc"), "10.1145/abc");
+    }
+}
@@ -0,0 +1,279 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tokio::time::Duration;
+
+use super::title_match::TitleCandidate;
+use super::{normalize_doi, send_with_retry, Enrichment, EnrichmentProvider, LookupKey, RateLimiter, Want};
+
+/// DOIs per `filter=doi:a|b|...` request; OpenAlex caps OR filters at 100.
+const CHUNK: usize = 50;
+/// Candidates fetched per title search.
+const TITLE_CANDIDATES: usize = 5;
+const SELECT: &str = "id,doi,title,publication_year,authorships,cited_by_count,abstract_inverted_index,open_access";
+
+#[derive(Deserialize, Debug)]
+struct OpenAlexPage {
+    results: Vec<OpenAlexWork>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenAlexWork {
+    id: Option<String>,
+    doi: Option<String>,
+    cited_by_count: Option<i32>,
+    abstract_inverted_index: Option<HashMap<String, Vec<usize>>>,
+    open_access: Option<OpenAlexOpenAccess>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    publication_year: Option<i32>,
+    #[serde(default)]
+    authorships: Vec<OpenAlexAuthorship>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenAlexAuthorship {
+    author: OpenAlexAuthor,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenAlexAuthor {
+    display_name: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenAlexOpenAccess {
+    oa_url: Option<String>,
+}
+
+pub struct OpenAlexClient {
+    http: reqwest::Client,
+    base_url: String,
+    mailto: Option<String>,
+    // OpenAlex allows 10 req/s; stay well below it.
+    limiter: RateLimiter,
+}
+
+impl OpenAlexClient {
+    pub fn new(base_url: String, mailto: Option<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url,
+            mailto,
+            limiter: RateLimiter::new(Duration::from_millis(200)),
+        }
+    }
+
+    async fn works_by_doi(&self, dois: &[String]) -> Vec<OpenAlexWork> {
+        let filter = format!("doi:{}", dois.join("|"));
+        self.works(&[("filter", filter.as_str())], dois.len()).await
+    }
+
+    /// GET `/works` with extra query parameters, returning up to `per_page` works.
+    async fn works(&self, params: &[(&str, &str)], per_page: usize) -> Vec<OpenAlexWork> {
+        let url = format!("{}/works", self.base_url.trim_end_matches('/'));
+        let per_page = per_page.to_string();
+
+        let resp = send_with_retry("OpenAlex", &self.limiter, || {
+            let mut req = self
+                .http
+                .get(&url)
+                .query(params)
+                .query(&[("per-page", per_page.as_str()), ("select", SELECT)])
+                .header("User-Agent", "CSCS/1.0");
+            if let Some(ref mailto) = self.mailto {
+                req = req.query(&[("mailto", mailto)]);
+            }
+            req
+        })
+        .await;
+
+        let Some(resp) = resp else {
+            return Vec::new();
+        };
+        match resp.json::<OpenAlexPage>().await {
+            Ok(page) => page.results,
+            Err(e) => {
+                eprintln!("OpenAlex JSON parse error: {}", e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+fn to_enrichment(work: &OpenAlexWork, want: Want) -> Enrichment {
+    Enrichment {
+        citation_count: work.cited_by_count.filter(|_| want.citations),
+        abstract_text: work
+            .abstract_inverted_index
+            .as_ref()
+            .filter(|_| want.abstracts)
+            .map(rebuild_abstract),
+        references: None,
+        open_access_url: work.open_access.as_ref().and_then(|o| o.oa_url.clone()),
+        // "https://openalex.org/W123" -> "W123"
+        external_id: work.id.as_deref().map(|id| id.rsplit('/').next().unwrap_or(id).to_string()),
+    }
+}
+
+/// OpenAlex ships abstracts as `word -> [positions]`; put the words back in order.
+fn rebuild_abstract(index: &HashMap<String, Vec<usize>>) -> String {
+    let mut words: Vec<(usize, &str)> = index
+        .iter()
+        .flat_map(|(word, positions)| positions.iter().map(move |p| (*p, word.as_str())))
+        .collect();
+    words.sort_by_key(|(p, _)| *p);
+    words.into_iter().map(|(_, w)| w).collect::<Vec<_>>().join(" ")
+}
+
+#[async_trait]
+impl EnrichmentProvider for OpenAlexClient {
+    fn name(&self) -> &'static str {
+        "openalex"
+    }
+
+    async fn lookup(&self, keys: &[LookupKey], want: Want) -> Vec<Option<Enrichment>> {
+        let targets: Vec<(usize, String)> = keys
+            .iter()
+            .enumerate()
+            .filter_map(|(i, k)| Some((i, normalize_doi(k.doi.as_ref()?))))
+            // The filter has no escaping, so a DOI containing its separator
+            // would split into bogus ones; leave those papers unmatched.
+            .filter(|(_, doi)| !doi.contains('|'))
+            .collect();
+
+        let mut out: Vec<Option<Enrichment>> = keys.iter().map(|_| None).collect();
+        for chunk in targets.chunks(CHUNK) {
+            let dois: Vec<String> = chunk.iter().map(|(_, d)| d.clone()).collect();
+
+            // Results aren't positional, so match them back by DOI.
+            let by_doi: HashMap<String, OpenAlexWork> = self
+                .works_by_doi(&dois)
+                .await
+                .into_iter()
+                .filter_map(|w| Some((normalize_doi(w.doi.as_ref()?), w)))
+                .collect();
+
+            for (idx, doi) in chunk {
+                if let Some(work) = by_doi.get(doi) {
+                    out[*idx] = Some(to_enrichment(work, want));
+                }
+            }
+        }
+        out
+    }
+
+    async fn search_title(&self, key: &LookupKey, want: Want) -> Vec<TitleCandidate> {
+        let mut filter = String::new();
+        if key.year > 0 {
+            filter = format!("publication_year:{}-{}", key.year - 1, key.year + 1);
+        }
+        let mut params = vec![("search", key.title.as_str())];
+        if !filter.is_empty() {
+            params.push(("filter", filter.as_str()));
+        }
+
+        self.works(&params, TITLE_CANDIDATES)
+            .await
+            .into_iter()
+            .map(|work| TitleCandidate {
+                title: work.title.clone().unwrap_or_default(),
+                year: work.publication_year,
+                authors: work
+                    .authorships
+                    .iter()
+                    .filter_map(|a| a.author.display_name.clone())
+                    .collect(),
+                enrichment: to_enrichment(&work, want),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+
+    const WORKS: &str = r#"{"results": [
+        {"doi": "https://doi.org/10.1/B", "cited_by_count": 9,
+         "abstract_inverted_index": {"world": [1], "hello": [0]},
+         "open_access": {"oa_url": "https://arxiv.org/pdf/2101.00001"}}
+    ]}"#;
+
+    #[tokio::test]
+    async fn lookup_matches_results_by_doi() {
+        let mock = MockServer::start(vec![(200, WORKS)]).await;
+        let client = OpenAlexClient::new(mock.base_url.clone(), Some("me@example.org".into()));
+        let keys = vec![
+            LookupKey { doi: Some("10.1/a".into()), ..Default::default() },
+            LookupKey::default(),
+            LookupKey { doi: Some("10.1/b".into()), ..Default::default() },
+        ];
+
+        let res = client.lookup(&keys, Want { citations: true, abstracts: true }).await;
+
+        assert!(res[0].is_none());
+        assert!(res[1].is_none());
+        let hit = res[2].as_ref().unwrap();
+        assert_eq!(hit.citation_count, Some(9));
+        assert_eq!(hit.abstract_text.as_deref(), Some("hello world"));
+        assert_eq!(hit.open_access_url.as_deref(), Some("https://arxiv.org/pdf/2101.00001"));
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].0.contains("filter=doi%3A10.1%2Fa%7C10.1%2Fb"));
+        assert!(requests[0].0.contains("mailto=me%40example.org"));
+    }
+
+    #[tokio::test]
+    async fn lookup_skips_dois_containing_the_separator() {
+        let mock = MockServer::start(vec![(200, WORKS)]).await;
+        let client = OpenAlexClient::new(mock.base_url.clone(), None);
+        let keys = vec![
+            LookupKey { doi: Some("10.1/x|y".into()), ..Default::default() },
+            LookupKey { doi: Some("10.1/b".into()), ..Default::default() },
+        ];
+
+        let res = client.lookup(&keys, Want { citations: true, abstracts: false }).await;
+
+        assert!(res[0].is_none());
+        assert_eq!(res[1].as_ref().unwrap().citation_count, Some(9));
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].0.contains("filter=doi%3A10.1%2Fb&"));
+    }
+
+    #[tokio::test]
+    async fn lookup_retries_server_errors() {
+        let mock = MockServer::start(vec![(503, ""), (200, WORKS)]).await;
+        let client = OpenAlexClient::new(mock.base_url.clone(), None);
+        let keys = vec![LookupKey { doi: Some("10.1/b".into()), ..Default::default() }];
+
+        let res = client.lookup(&keys, Want { citations: true, abstracts: false }).await;
+
+        let hit = res[0].as_ref().unwrap();
+        assert_eq!(hit.citation_count, Some(9));
+        assert!(hit.abstract_text.is_none());
+        assert_eq!(mock.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn search_title_filters_by_year_window() {
+        let mock = MockServer::start(vec![(200, r#"{"results": [
+            {"doi": null, "title": "Beta k-means", "publication_year": 2021, "cited_by_count": 2,
+             "authorships": [{"author": {"display_name": "Ann One"}}]}
+        ]}"#)]).await;
+        let client = OpenAlexClient::new(mock.base_url.clone(), None);
+        let key = LookupKey { title: "Beta k-means.".into(), year: 2021, ..Default::default() };
+
+        let found = client.search_title(&key, Want { citations: true, abstracts: true }).await;
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].year, Some(2021));
+        assert_eq!(found[0].authors, vec!["Ann One".to_string()]);
+        assert_eq!(found[0].enrichment.citation_count, Some(2));
+        assert!(mock.requests()[0].0.contains("filter=publication_year%3A2020-2022"));
+    }
+}
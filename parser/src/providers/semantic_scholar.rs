@@ -0,0 +1,347 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tokio::time::Duration;
+
+use super::title_match::TitleCandidate;
+use super::{send_with_retry, Enrichment, EnrichmentProvider, LookupKey, RateLimiter, Want};
+
+// Semantic Scholar's `/paper/batch` returns a JSON array positionally aligned
+// with the requested ids (null for unknown papers). Abstracts come back as
+// plain text, so no inverted-index reconstruction is needed.
+#[derive(Deserialize, Debug)]
+pub struct SemanticScholarPaper {
+    #[serde(rename = "paperId", default)]
+    pub paper_id: Option<String>,
+    #[serde(rename = "citationCount")]
+    pub citation_count: Option<i32>,
+    #[serde(rename = "abstract")]
+    pub abstract_text: Option<String>,
+    #[serde(default)]
+    pub references: Option<Vec<SemanticScholarReference>>,
+    #[serde(rename = "openAccessPdf", default)]
+    pub open_access_pdf: Option<SemanticScholarOpenAccessPdf>,
+    // Only requested for title searches.
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub year: Option<i32>,
+    #[serde(default)]
+    pub authors: Option<Vec<SemanticScholarAuthor>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SemanticScholarAuthor {
+    pub name: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct SemanticScholarMatches {
+    data: Vec<SemanticScholarPaper>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SemanticScholarOpenAccessPdf {
+    pub url: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SemanticScholarReference {
+    #[serde(rename = "externalIds")]
+    pub external_ids: Option<HashMap<String, serde_json::Value>>,
+}
+
+/// Connection details for the Semantic Scholar Graph API. The base URL is
+/// configurable so the pipeline can be pointed at a local mock.
+pub struct SemanticScholarClient {
+    http: reqwest::Client,
+    api_key: Option<String>,
+    base_url: String,
+    // 1100ms leaves headroom under the ~1 req/s limit.
+    limiter: RateLimiter,
+}
+
+impl SemanticScholarClient {
+    pub fn new(api_key: Option<String>, base_url: String) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_key,
+            base_url,
+            limiter: RateLimiter::new(Duration::from_millis(1100)),
+        }
+    }
+
+    fn fields(want: Want) -> String {
+        let mut fields: Vec<&str> = Vec::new();
+        if want.citations {
+            fields.push("citationCount");
+            fields.push("references.externalIds");
+        }
+        if want.abstracts {
+            fields.push("abstract");
+        }
+        fields.push("openAccessPdf");
+        fields.join(",")
+    }
+}
+
+fn to_enrichment(paper: SemanticScholarPaper) -> Enrichment {
+    Enrichment {
+        citation_count: paper.citation_count,
+        abstract_text: paper.abstract_text,
+        references: paper.references.map(|refs| {
+            refs.into_iter()
+                .filter_map(|r| r.external_ids?.get("DBLP")?.as_str().map(str::to_string))
+                .collect()
+        }),
+        open_access_url: paper.open_access_pdf.and_then(|p| p.url),
+        external_id: paper.paper_id,
+    }
+}
+
+#[async_trait]
+impl EnrichmentProvider for SemanticScholarClient {
+    fn name(&self) -> &'static str {
+        "semantic_scholar"
+    }
+
+    async fn lookup(&self, keys: &[LookupKey], want: Want) -> Vec<Option<Enrichment>> {
+        let fields = Self::fields(want);
+        let targets: Vec<(usize, String)> = keys
+            .iter()
+            .enumerate()
+            .filter_map(|(i, k)| Some((i, format!("DOI:{}", k.doi.as_ref()?))))
+            .collect();
+
+        let mut out: Vec<Option<Enrichment>> = keys.iter().map(|_| None).collect();
+        // The batch endpoint accepts up to 500 ids per request.
+        for chunk in targets.chunks(500) {
+            let ids: Vec<String> = chunk.iter().map(|(_, id)| id.clone()).collect();
+            let results = semantic_scholar_batch(self, &ids, &fields).await;
+
+            for ((idx, _), res) in chunk.iter().zip(results) {
+                out[*idx] = res.map(to_enrichment);
+            }
+        }
+        out
+    }
+
+    /// `/paper/search/match` returns Semantic Scholar's single best title match.
+    async fn search_title(&self, key: &LookupKey, want: Want) -> Vec<TitleCandidate> {
+        let url = format!("{}/paper/search/match", self.base_url.trim_end_matches('/'));
+        let fields = format!("title,year,authors,{}", Self::fields(want));
+
+        let resp = send_with_retry("Semantic Scholar", &self.limiter, || {
+            let mut req = self
+                .http
+                .get(&url)
+                .query(&[("query", key.title.as_str()), ("fields", fields.as_str())])
+                .header("User-Agent", "CSCS/1.0");
+            if let Some(ref api_key) = self.api_key {
+                req = req.header("x-api-key", api_key);
+            }
+            req
+        })
+        .await;
+
+        let Some(resp) = resp else {
+            return Vec::new();
+        };
+        let matches = match resp.json::<SemanticScholarMatches>().await {
+            Ok(m) => m.data,
+            Err(e) => {
+                eprintln!("Semantic Scholar JSON parse error (title match): {}", e);
+                return Vec::new();
+            }
+        };
+
+        matches
+            .into_iter()
+            .map(|mut paper| TitleCandidate {
+                title: paper.title.take().unwrap_or_default(),
+                year: paper.year,
+                authors: paper
+                    .authors
+                    .take()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|a| a.name)
+                    .collect(),
+                enrichment: to_enrichment(paper),
+            })
+            .collect()
+    }
+}
+
+/// POST a batch of Semantic Scholar ids (e.g. "DOI:10.1145/...") and return the
+/// papers positionally aligned with `ids` (None for unknown / failed lookups).
+pub async fn semantic_scholar_batch(
+    s2: &SemanticScholarClient,
+    ids: &[String],
+    fields: &str,
+) -> Vec<Option<SemanticScholarPaper>> {
+    let url = format!("{}/paper/batch", s2.base_url.trim_end_matches('/'));
+    let body = serde_json::json!({ "ids": ids });
+
+    let resp = send_with_retry("Semantic Scholar", &s2.limiter, || {
+        let mut req = s2
+            .http
+            .post(&url)
+            .query(&[("fields", fields)])
+            .header("User-Agent", "CSCS/1.0")
+            .json(&body);
+        if let Some(ref key) = s2.api_key {
+            req = req.header("x-api-key", key);
+        }
+        req
+    })
+    .await;
+
+    let Some(resp) = resp else {
+        return Vec::new();
+    };
+    match resp.json::<Vec<Option<SemanticScholarPaper>>>().await {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Semantic Scholar JSON parse error ({}): {}", fields, e);
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+
+    const BATCH_OK: &str = r#"[
+        {"citationCount": 42, "abstract": "We present Raft.", "references": [
+            {"externalIds": {"DBLP": "conf/osdi/LamportX01", "CorpusId": 1}},
+            {"externalIds": null}
+        ]},
+        null
+    ]"#;
+
+    fn ids() -> Vec<String> {
+        vec!["DOI:10.1/a".to_string(), "DOI:10.1/b".to_string()]
+    }
+
+    #[tokio::test]
+    async fn batch_parses_positionally_aligned_results() {
+        let mock = MockServer::start(vec![(200, BATCH_OK)]).await;
+        let s2 = SemanticScholarClient::new(Some("key".into()), mock.base_url.clone());
+
+        let res = semantic_scholar_batch(&s2, &ids(), "citationCount,abstract").await;
+
+        assert_eq!(res.len(), 2);
+        let first = res[0].as_ref().unwrap();
+        assert_eq!(first.citation_count, Some(42));
+        assert_eq!(first.abstract_text.as_deref(), Some("We present Raft."));
+        assert_eq!(first.references.as_ref().unwrap().len(), 2);
+        assert!(res[1].is_none());
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].0.starts_with("/paper/batch?fields=citationCount%2Cabstract"));
+        assert!(requests[0].1.contains("DOI:10.1/a"));
+    }
+
+    #[tokio::test]
+    async fn batch_retries_rate_limits_and_server_errors() {
+        let mock = MockServer::start(vec![(429, "slow down"), (503, "unavailable"), (200, BATCH_OK)]).await;
+        let s2 = SemanticScholarClient::new(None, mock.base_url.clone());
+
+        let res = semantic_scholar_batch(&s2, &ids(), "citationCount").await;
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn batch_gives_up_after_three_attempts() {
+        let mock = MockServer::start(vec![(500, ""), (502, ""), (429, ""), (200, BATCH_OK)]).await;
+        let s2 = SemanticScholarClient::new(None, mock.base_url.clone());
+
+        let started = std::time::Instant::now();
+        let res = semantic_scholar_batch(&s2, &ids(), "citationCount").await;
+
+        assert!(res.is_empty());
+        assert_eq!(mock.requests().len(), 3);
+        // Retries wait for the 1.1s limiter as well as the backoff, and there
+        // is no further sleep after the last attempt.
+        let elapsed = started.elapsed();
+        assert!(elapsed >= std::time::Duration::from_millis(2200));
+        assert!(elapsed < std::time::Duration::from_millis(3500));
+    }
+
+    #[tokio::test]
+    async fn batch_does_not_retry_client_errors() {
+        let mock = MockServer::start(vec![(400, "bad request"), (200, BATCH_OK)]).await;
+        let s2 = SemanticScholarClient::new(None, mock.base_url.clone());
+
+        let res = semantic_scholar_batch(&s2, &ids(), "citationCount").await;
+
+        assert!(res.is_empty());
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn batch_returns_nothing_on_malformed_json() {
+        let mock = MockServer::start(vec![(200, "{not json")]).await;
+        let s2 = SemanticScholarClient::new(None, mock.base_url.clone());
+
+        let res = semantic_scholar_batch(&s2, &ids(), "citationCount").await;
+
+        assert!(res.is_empty());
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn lookup_maps_dblp_references_and_skips_keys_without_doi() {
+        let mock = MockServer::start(vec![(200, r#"[
+            {"citationCount": 3, "references": [{"externalIds": {"DBLP": "conf/osdi/A20"}}, {"externalIds": {"DOI": "10.9/x"}}],
+             "openAccessPdf": {"url": "https://example.org/a.pdf"}}
+        ]"#)]).await;
+        let s2 = SemanticScholarClient::new(None, mock.base_url.clone());
+        let keys = vec![
+            LookupKey::default(),
+            LookupKey { doi: Some("10.1/a".into()), ..Default::default() },
+        ];
+
+        let res = s2.lookup(&keys, Want { citations: true, abstracts: false }).await;
+
+        assert!(res[0].is_none());
+        let hit = res[1].as_ref().unwrap();
+        assert_eq!(hit.citation_count, Some(3));
+        assert_eq!(hit.references, Some(vec!["conf/osdi/A20".to_string()]));
+        assert_eq!(hit.open_access_url.as_deref(), Some("https://example.org/a.pdf"));
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].1.contains("DOI:10.1/a"));
+        assert!(!requests[0].0.contains("abstract"));
+    }
+
+    #[tokio::test]
+    async fn search_title_returns_candidate_and_treats_404_as_no_match() {
+        let mock = MockServer::start(vec![
+            (200, r#"{"data": [{"title": "Alpha Systems", "year": 2020, "authors": [{"name": "Ann One"}],
+                       "citationCount": 11, "matchScore": 120.5}]}"#),
+            (404, r#"{"error": "Title match not found"}"#),
+        ]).await;
+        let s2 = SemanticScholarClient::new(None, mock.base_url.clone());
+        let key = LookupKey { title: "Alpha systems.".into(), year: 2020, ..Default::default() };
+        let want = Want { citations: true, abstracts: false };
+
+        let found = s2.search_title(&key, want).await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].title, "Alpha Systems");
+        assert_eq!(found[0].authors, vec!["Ann One".to_string()]);
+        assert_eq!(found[0].enrichment.citation_count, Some(11));
+
+        assert!(s2.search_title(&key, want).await.is_empty());
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].0.starts_with("/paper/search/match?query=Alpha+systems."));
+    }
+}
@@ -0,0 +1,160 @@
+//! Scoring of title-search candidates for papers that have no DOI. Providers
+//! return their best guesses; we decide which (if any) is the same paper.
+
+use super::{Enrichment, LookupKey};
+
+pub struct TitleCandidate {
+    pub title: String,
+    pub year: Option<i32>,
+    pub authors: Vec<String>,
+    pub enrichment: Enrichment,
+}
+
+/// Lowercase, drop punctuation and collapse whitespace, so "Raft: In Search of
+/// an Understandable Consensus Algorithm." and DBLP's spelling compare equal.
+pub fn normalize_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Sørensen–Dice coefficient over character bigrams of the normalised titles.
+/// Tolerant of small spelling and punctuation differences, strict on wording.
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let bigrams = |s: &str| -> Vec<(char, char)> {
+        let chars: Vec<char> = normalize_title(s).chars().collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect()
+    };
+    let a = bigrams(a);
+    let mut b = bigrams(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let total = a.len() + b.len();
+    let mut shared = 0;
+    for bigram in &a {
+        if let Some(pos) = b.iter().position(|x| x == bigram) {
+            b.swap_remove(pos);
+            shared += 1;
+        }
+    }
+    2.0 * shared as f64 / total as f64
+}
+
+fn surname(name: &str) -> String {
+    // DBLP disambiguates homonyms with a numeric suffix ("Wei Wang 0001").
+    name.split_whitespace()
+        .rfind(|part| !part.chars().all(|c| c.is_ascii_digit()))
+        .map(normalize_title)
+        .unwrap_or_default()
+}
+
+/// Confidence that `candidate` is the paper described by `key`, in `[0, 1]`.
+/// Title similarity is the base score; a year more than one off rules the
+/// candidate out (proceedings are sometimes dated the following year), and a
+/// first author missing from the candidate's author list costs 15%.
+pub fn match_confidence(key: &LookupKey, candidate: &TitleCandidate) -> f64 {
+    if let Some(year) = candidate.year {
+        if key.year > 0 && (year - key.year).abs() > 1 {
+            return 0.0;
+        }
+    }
+
+    let mut score = title_similarity(&key.title, &candidate.title);
+    if let Some(ref first) = key.first_author {
+        let wanted = surname(first);
+        if !candidate.authors.is_empty() && !candidate.authors.iter().any(|a| surname(a) == wanted) {
+            score *= 0.85;
+        }
+    }
+    score
+}
+
+/// The best candidate at or above `threshold`, with its confidence.
+pub fn best_match(
+    key: &LookupKey,
+    candidates: Vec<TitleCandidate>,
+    threshold: f64,
+) -> Option<(Enrichment, f64)> {
+    candidates
+        .into_iter()
+        .map(|c| {
+            let confidence = match_confidence(key, &c);
+            (c.enrichment, confidence)
+        })
+        .filter(|(_, confidence)| *confidence >= threshold)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> LookupKey {
+        LookupKey {
+            doi: None,
+            title: "In Search of an Understandable Consensus Algorithm.".into(),
+            year: 2014,
+            first_author: Some("Diego Ongaro".into()),
+        }
+    }
+
+    fn candidate(title: &str, year: i32, authors: &[&str]) -> TitleCandidate {
+        TitleCandidate {
+            title: title.into(),
+            year: Some(year),
+            authors: authors.iter().map(|a| a.to_string()).collect(),
+            enrichment: Enrichment::default(),
+        }
+    }
+
+    #[test]
+    fn normalize_title_ignores_case_and_punctuation() {
+        assert_eq!(normalize_title("Raft: In Search of  Consensus."), "raft in search of consensus");
+    }
+
+    #[test]
+    fn exact_title_with_matching_author_scores_one() {
+        let c = candidate("In search of an understandable consensus algorithm", 2014, &["Diego Ongaro", "John K. Ousterhout"]);
+        assert!((match_confidence(&key(), &c) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn year_far_off_is_rejected() {
+        let c = candidate("In search of an understandable consensus algorithm", 2019, &["Diego Ongaro"]);
+        assert_eq!(match_confidence(&key(), &c), 0.0);
+    }
+
+    #[test]
+    fn missing_first_author_is_penalised() {
+        let c = candidate("In search of an understandable consensus algorithm", 2015, &["Someone Else"]);
+        assert!((match_confidence(&key(), &c) - 0.85).abs() < 1e-9);
+    }
+
+    #[test]
+    fn dblp_homonym_suffix_is_ignored() {
+        let mut k = key();
+        k.first_author = Some("Wei Wang 0001".into());
+        let c = candidate("In search of an understandable consensus algorithm", 2014, &["Wei Wang"]);
+        assert!((match_confidence(&k, &c) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn best_match_applies_threshold() {
+        let candidates = vec![
+            candidate("A different paper about consensus", 2014, &["Diego Ongaro"]),
+            candidate("In Search of an Understandable Consensus Algorithm (Extended Version)", 2014, &["Diego Ongaro"]),
+        ];
+        let (_, confidence) = best_match(&key(), candidates, 0.8).unwrap();
+        assert!(confidence > 0.8);
+
+        let candidates = vec![candidate("A different paper about consensus", 2014, &["Diego Ongaro"])];
+        assert!(best_match(&key(), candidates, 0.8).is_none());
+    }
+}
@@ -0,0 +1,299 @@
+//! Postgres-backed enrichment queue. `--enqueue` imports fill it instead of
+//! calling providers inline; `--worker` drains it in small batches. Each batch
+//! is leased, enriched, written back and then deleted from the queue, so a
+//! crashed or restarted worker picks up where it stopped (its lease simply
+//! expires) and several workers can share one queue. Papers no provider
+//! answered (rate limits, outages) stay queued and are retried later.
+
+use anyhow::Result;
+use sqlx::{Pool, Postgres};
+use tokio::time::Duration;
+
+use crate::providers::{EnrichmentProvider, Provenance, Want};
+use crate::{
+    build_providers, collect_citation_edges, collect_citing, collect_oa_links, collect_provenance, enrich_batch,
+    insert_citations, max_age_days, title_threshold, upsert_oa_links, upsert_provenance, Paper,
+};
+
+/// Papers claimed per batch. Small enough that title searches (one request
+/// per paper) finish well within the lease.
+const BATCH: i64 = 100;
+/// How long a claimed batch stays invisible to other workers.
+const LEASE_MINUTES: i32 = 10;
+/// Give up on a paper after this many failed batches; it stays in the queue
+/// with `last_error` set for inspection.
+const MAX_ATTEMPTS: i32 = 5;
+/// Idle wait between polls in `--follow` mode.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Queue the papers with these DBLP keys. Re-queueing a pending paper widens
+/// its wanted fields and resets its failure count.
+pub async fn enqueue(pool: &Pool<Postgres>, dblp_keys: &[String], want: Want) -> Result<()> {
+    if dblp_keys.is_empty() || (!want.citations && !want.abstracts) {
+        return Ok(());
+    }
+
+    sqlx::query(
+        "INSERT INTO enrichment_queue (paper_id, want_citations, want_abstract) \
+         SELECT id, $2, $3 FROM papers WHERE dblp_key = ANY($1) \
+         ON CONFLICT (paper_id) DO UPDATE SET \
+         want_citations = enrichment_queue.want_citations OR EXCLUDED.want_citations, \
+         want_abstract = enrichment_queue.want_abstract OR EXCLUDED.want_abstract, \
+         attempts = 0, \
+         last_error = NULL"
+    )
+    .bind(dblp_keys)
+    .bind(want.citations)
+    .bind(want.abstracts)
+    .execute(pool).await?;
+
+    Ok(())
+}
+
+/// Drain the queue: `parser --worker [--follow] [--max-age-days N]`. Without
+/// `--follow` the worker exits once nothing is left to claim; with it, it keeps
+/// polling. With `--max-age-days`, queued papers enriched within the last N
+/// days leave the queue without a lookup, so refresh imports that re-queue
+/// every paper only spend requests on new and stale ones.
+pub async fn run_worker(pool: &Pool<Postgres>, args: &[String]) -> Result<()> {
+    let providers = build_providers(args)?;
+    let title_threshold = title_threshold(args);
+    let max_age_days = max_age_days(args)?;
+    let follow = args.contains(&"--follow".to_string());
+
+    let (pending,): (i64,) = sqlx::query_as("SELECT count(*) FROM enrichment_queue WHERE attempts < $1")
+        .bind(MAX_ATTEMPTS)
+        .fetch_one(pool).await?;
+    println!("Enrichment worker started, {} papers queued", pending);
+
+    let mut processed = 0;
+    loop {
+        let claimed = claim(pool).await?;
+        if claimed.is_empty() {
+            if follow {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+            break;
+        }
+
+        processed += work_batch(pool, &providers, &claimed, title_threshold, max_age_days).await?;
+        println!("Enriched {} papers", processed);
+    }
+
+    let (failed,): (i64,) = sqlx::query_as("SELECT count(*) FROM enrichment_queue WHERE attempts >= $1")
+        .bind(MAX_ATTEMPTS)
+        .fetch_one(pool).await?;
+    if failed > 0 {
+        println!("{} papers gave up after {} attempts; see enrichment_queue.last_error", failed, MAX_ATTEMPTS);
+    }
+    Ok(())
+}
+
+/// Lease the next batch, oldest first. SKIP LOCKED keeps concurrent workers
+/// from claiming the same rows.
+async fn claim(pool: &Pool<Postgres>) -> Result<Vec<(i32, bool, bool)>> {
+    let rows = sqlx::query_as(
+        "UPDATE enrichment_queue SET locked_until = now() + make_interval(mins => $2) \
+         WHERE paper_id IN ( \
+             SELECT paper_id FROM enrichment_queue \
+             WHERE (locked_until IS NULL OR locked_until < now()) AND attempts < $3 \
+             ORDER BY enqueued_at, paper_id \
+             LIMIT $1 \
+             FOR UPDATE SKIP LOCKED) \
+         RETURNING paper_id, want_citations, want_abstract"
+    )
+    .bind(BATCH)
+    .bind(LEASE_MINUTES)
+    .bind(MAX_ATTEMPTS)
+    .fetch_all(pool).await?;
+    Ok(rows)
+}
+
+/// Enrich a claimed batch and settle its queue rows: papers that were enriched
+/// (or had nothing to look up) leave the queue; papers no provider answered,
+/// and whole groups that failed, count an attempt and are released for a
+/// later claim. Returns how many papers left the queue.
+async fn work_batch(
+    pool: &Pool<Postgres>,
+    providers: &[Box<dyn EnrichmentProvider>],
+    claimed: &[(i32, bool, bool)],
+    title_threshold: Option<f64>,
+    max_age_days: Option<i32>,
+) -> Result<usize> {
+    let mut done = 0;
+    // enrich_batch takes one set of wanted fields, so split by them.
+    for (want_cite, want_abstract) in [(true, true), (true, false), (false, true)] {
+        let ids: Vec<i32> = claimed
+            .iter()
+            .filter(|c| c.1 == want_cite && c.2 == want_abstract)
+            .map(|c| c.0)
+            .collect();
+        if ids.is_empty() {
+            continue;
+        }
+
+        let (finished, retry, error) =
+            match process(pool, providers, &ids, want_cite, want_abstract, title_threshold, max_age_days).await {
+                Ok(unanswered) => {
+                    let finished: Vec<i32> = ids.iter().copied().filter(|id| !unanswered.contains(id)).collect();
+                    (finished, unanswered, "no provider answered".to_string())
+                }
+                Err(e) => {
+                    eprintln!("Error enriching queued batch: {}", e);
+                    (Vec::new(), ids, e.to_string())
+                }
+            };
+
+        sqlx::query("DELETE FROM enrichment_queue WHERE paper_id = ANY($1)")
+            .bind(&finished)
+            .execute(pool).await?;
+        sqlx::query(
+            "UPDATE enrichment_queue SET attempts = attempts + 1, last_error = $2, locked_until = NULL \
+             WHERE paper_id = ANY($1)"
+        )
+        .bind(&retry)
+        .bind(error)
+        .execute(pool).await?;
+        done += finished.len();
+    }
+    Ok(done)
+}
+
+/// Enrich and write back the given papers. Returns the ids of papers no
+/// provider answered.
+async fn process(
+    pool: &Pool<Postgres>,
+    providers: &[Box<dyn EnrichmentProvider>],
+    ids: &[i32],
+    want_cite: bool,
+    want_abstract: bool,
+    title_threshold: Option<f64>,
+    max_age_days: Option<i32>,
+) -> Result<Vec<i32>> {
+    let (paper_ids, mut batch): (Vec<i32>, Vec<Paper>) = load_papers(pool, ids).await?.into_iter().unzip();
+    let unanswered = enrich_batch(pool, providers, &mut batch, want_cite, want_abstract, title_threshold, max_age_days).await?;
+    let unanswered: Vec<i32> = unanswered.into_iter().map(|idx| paper_ids[idx]).collect();
+    save(pool, &batch).await?;
+
+    // Every paper is already in Postgres, so unresolved edges point outside
+    // the index and can be dropped.
+    insert_citations(pool, &collect_citing(&batch), &collect_citation_edges(&batch)).await?;
+    upsert_provenance(pool, &collect_provenance(&batch)).await?;
+    upsert_oa_links(pool, &collect_oa_links(&batch)).await?;
+    Ok(unanswered)
+}
+
+/// (id, dblp_key, title, year, ee links, authors in order)
+type QueuedPaperRow = (i32, String, String, Option<i32>, Vec<Option<String>>, Vec<String>);
+
+/// Rebuild enough of a `Paper` from Postgres for `enrich_batch` to look it up.
+async fn load_papers(pool: &Pool<Postgres>, ids: &[i32]) -> Result<Vec<(i32, Paper)>> {
+    let rows: Vec<QueuedPaperRow> = sqlx::query_as(
+        "SELECT p.id, p.dblp_key, p.title, p.year, \
+                COALESCE(NULLIF(ARRAY(SELECT l.url FROM paper_links l WHERE l.paper_id = p.id AND l.source = 'dblp'), '{}'), ARRAY[p.ee_link]), \
+                COALESCE(array_agg(a.name ORDER BY pa.author_order) FILTER (WHERE a.name IS NOT NULL), '{}') \
+         FROM papers p \
+         LEFT JOIN paper_authors pa ON pa.paper_id = p.id \
+         LEFT JOIN authors a ON a.id = pa.author_id \
+         WHERE p.id = ANY($1) \
+         GROUP BY p.id"
+    )
+    .bind(ids)
+    .fetch_all(pool).await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(id, dblp_key, title, year, ee_links, authors)| (id, Paper {
+            title,
+            title_html: String::new(),
+            year: year.unwrap_or(0),
+            authors,
+            venue: String::new(),
+            dblp_key,
+            ee_links: ee_links.into_iter().flatten().collect(),
+            bib: Default::default(),
+            paper_type: cscs_core::PaperType::Full,
+            citation_count: None,
+            abstract_text: None,
+            open_access_url: None,
+            references: None,
+            match_method: None,
+            match_confidence: None,
+            provenance: Provenance::default(),
+        }))
+        .collect())
+}
+
+/// Write enriched fields back, keeping existing values where nothing new came in.
+async fn save(pool: &Pool<Postgres>, batch: &[Paper]) -> Result<()> {
+    let dblp_keys: Vec<&str> = batch.iter().map(|p| p.dblp_key.as_str()).collect();
+    let cit_counts: Vec<Option<i32>> = batch.iter().map(|p| p.citation_count).collect();
+    let abstracts: Vec<Option<&str>> = batch.iter().map(|p| p.abstract_text.as_deref()).collect();
+    let match_methods: Vec<Option<&str>> = batch.iter().map(|p| p.match_method).collect();
+    let match_confidences: Vec<Option<f32>> = batch.iter().map(|p| p.match_confidence).collect();
+
+    sqlx::query(
+        "UPDATE papers SET \
+         citation_count = CASE WHEN v.citation_count > 0 THEN v.citation_count ELSE papers.citation_count END, \
+         abstract = COALESCE(v.abstract, papers.abstract), \
+         match_method = COALESCE(v.match_method, papers.match_method), \
+         match_confidence = COALESCE(v.match_confidence, papers.match_confidence) \
+         FROM (SELECT * FROM UNNEST($1::text[], $2::int[], $3::text[], $4::text[], $5::real[])) \
+         AS v(dblp_key, citation_count, abstract, match_method, match_confidence) \
+         WHERE papers.dblp_key = v.dblp_key"
+    )
+    .bind(&dblp_keys)
+    .bind(&cit_counts)
+    .bind(&abstracts)
+    .bind(&match_methods)
+    .bind(&match_confidences)
+    .execute(pool).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+    use crate::providers;
+
+    /// Needs a migrated Postgres at DATABASE_URL; skipped without one.
+    #[tokio::test]
+    async fn unanswered_papers_stay_queued() {
+        let Ok(url) = std::env::var("DATABASE_URL") else {
+            eprintln!("DATABASE_URL not set; skipping");
+            return;
+        };
+        cscs_core::db::migrate(&url).await.unwrap();
+        let pool = cscs_core::db::connect(&url, 2).await.unwrap();
+        let key = "test/queue/Unanswered503";
+        sqlx::query("DELETE FROM papers WHERE dblp_key = $1").bind(key).execute(&pool).await.unwrap();
+        let (id,): (i32,) = sqlx::query_as(
+            "INSERT INTO papers (title, title_html, year, ee_link, dblp_key) \
+             VALUES ('Queued Paper', 'Queued Paper', 2020, 'https://doi.org/10.1/queued', $1) RETURNING id"
+        )
+        .bind(key)
+        .fetch_one(&pool).await.unwrap();
+        enqueue(&pool, &[key.to_string()], Want { citations: true, abstracts: false }).await.unwrap();
+
+        let mock = MockServer::start(vec![(503, ""), (503, ""), (503, "")]).await;
+        let providers = providers::from_names("semantic_scholar", Some(mock.base_url.clone())).unwrap();
+        let done = work_batch(&pool, &providers, &[(id, true, false)], None, None).await.unwrap();
+
+        let row: Option<(i32, Option<String>, bool)> = sqlx::query_as(
+            "SELECT attempts, last_error, locked_until IS NULL FROM enrichment_queue WHERE paper_id = $1"
+        )
+        .bind(id)
+        .fetch_optional(&pool).await.unwrap();
+        sqlx::query("DELETE FROM papers WHERE id = $1").bind(id).execute(&pool).await.unwrap();
+
+        assert_eq!(done, 0);
+        assert_eq!(mock.requests().len(), 3);
+        let (attempts, last_error, released) = row.expect("paper left the queue");
+        assert_eq!(attempts, 1);
+        assert_eq!(last_error.as_deref(), Some("no provider answered"));
+        assert!(released);
+    }
+}
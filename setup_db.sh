@@ -15,7 +15,9 @@ else
     docker exec $CONTAINER_NAME createdb -U $DB_USER $DB_NAME
 fi
 
-echo "Executing DDL (schema.sql)..."
-cat schema.sql | docker exec -i $CONTAINER_NAME psql -U $DB_USER -d $DB_NAME
+# The schema is versioned in core/migrations/ and applied by the parser (and the
+# backend at startup); this brings an existing database up to date as well.
+echo "Applying schema migrations..."
+(cd parser && cargo run --release -- --migrate)
 
-echo "Database setup complete."
\ No newline at end of file
+echo "Database setup complete."
@@ -1,33 +1,32 @@
+use anyhow::Context;
+use cscs_core::embedder::{Embedder, EMBEDDER_NAME};
+use cscs_core::{Config, PaperDoc, cache, db, papers};
 use meilisearch_sdk::client::Client;
 use meilisearch_sdk::indexes::Index;
-use serde::{Deserialize, Serialize};
-use sqlx::postgres::PgPoolOptions;
-
-#[derive(Serialize, Deserialize, Debug)]
-struct PaperDoc {
-    id: i32,
-    title: String,
-    year: i32,
-    venue: String,
-    authors: Vec<String>,
-    ee_link: Option<String>,
-    dblp_key: String,
-    citation_count: i32,
-    abstract_text: Option<String>,
-}
+use meilisearch_sdk::settings::{Embedder as EmbedderSettings, EmbedderSource};
+use std::collections::HashMap;
+
+/// How long to wait for Meilisearch to work through the queued batches.
+const INDEXING_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(6 * 3600);
+/// Texts per embedding request; keeps CPU-served models within their batch size.
+const EMBED_CHUNK: usize = 64;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    dotenvy::dotenv().ok();
-    let db_url = std::env::var("DATABASE_URL")?;
-    let pool = PgPoolOptions::new().connect(&db_url).await?;
+    let config = Config::from_env()?;
+    // Sync only reads; leave migrating to the parser and backend.
+    db::check(&config.database_url).await?;
+    let pool = db::connect(&config.database_url, 10).await?;
 
-    let meili_url = std::env::var("MEILI_URL").unwrap_or_else(|_| "http://localhost:7700".into());
-    let meili_key = std::env::var("MEILI_MASTER_KEY").expect("MEILI_MASTER_KEY must be set");
-    let meili = Client::new(meili_url, Some(meili_key))?;
+    let meili = Client::new(&config.meili_url, Some(config.meili_key()?))?;
     let papers_index = meili.index("papers");
 
-    setup_meili_settings(&meili, &papers_index).await?;
+    let embedder = Embedder::from_env();
+    if embedder.is_none() {
+        println!("EMBEDDER_URL not set; indexing without vectors (keyword search only).");
+    }
+
+    setup_meili_settings(&meili, &papers_index, embedder.as_ref()).await?;
 
     let mut last_id: i32 = 0;
     let batch_size: i64 = 10000;
@@ -35,62 +34,98 @@ async fn main() -> anyhow::Result<()> {
 
     println!("Start indexing...");
 
+    let mut last_task = None;
     loop {
-        let rows = sqlx::query!(
-            r#"
-            SELECT p.id, p.title, p.year, p.ee_link, p.dblp_key, p.citation_count, p.abstract as abstract_text, v.raw_name as venue, 
-                   ARRAY_AGG(a.name ORDER BY pa.author_order) as "authors!"
-            FROM papers p
-            JOIN venues v ON p.venue_id = v.id
-            JOIN paper_authors pa ON p.id = pa.paper_id
-            JOIN authors a ON pa.author_id = a.id
-            WHERE p.id > $1
-            GROUP BY p.id, v.raw_name, p.ee_link, p.dblp_key, p.citation_count, p.abstract
-            ORDER BY p.id ASC
-            LIMIT $2
-            "#,
-            last_id,
-            batch_size
-        )
-        .fetch_all(&pool)
-        .await?;
+        let mut docs = papers::fetch_after(&pool, last_id, batch_size).await?;
 
-        if rows.is_empty() {
+        if docs.is_empty() {
             break;
         }
 
-        // Advance the keyset cursor; rows are ordered by p.id ASC.
-        last_id = rows.last().map(|r| r.id).unwrap_or(last_id);
-
-        let docs: Vec<PaperDoc> = rows
-            .into_iter()
-            .map(|r| PaperDoc {
-                id: r.id,
-                title: r.title,
-                year: r.year.unwrap_or(0),
-                venue: r.venue,
-                authors: r.authors,
-                ee_link: r.ee_link,
-                dblp_key: r.dblp_key,
-                citation_count: r.citation_count.unwrap_or(0),
-                abstract_text: r.abstract_text,
-            })
-            .collect();
+        // Advance the keyset cursor; pages are ordered by id.
+        last_id = docs.last().map(|d| d.id).unwrap_or(last_id);
+
+        if let Some(ref embedder) = embedder {
+            embed_docs(embedder, &mut docs).await?;
+        }
 
-        papers_index.add_documents(&docs, Some("id")).await?;
+        last_task = Some(papers_index.add_documents(&docs, Some("id")).await?);
 
         total += docs.len() as i64;
         println!("Indexed total: {} papers", total);
     }
 
+    // Papers a newer dump dropped were deleted by the parser; drop them from
+    // the index too. The rows are cleared only once the deletion has succeeded,
+    // so a failed task is retried by the next sync.
+    let removed: Vec<i32> = sqlx::query_scalar("SELECT paper_id FROM removed_papers")
+        .fetch_all(&pool)
+        .await?;
+    if !removed.is_empty() {
+        last_task = Some(papers_index.delete_documents(&removed).await?);
+    }
+
+    println!("Refreshing term statistics...");
+    sqlx::query("REFRESH MATERIALIZED VIEW term_stats")
+        .execute(&pool)
+        .await?;
+
+    // Meilisearch applies document batches in order, so once the last one is
+    // done the index reflects this run and cached responses can be dropped.
+    if let Some(task) = last_task {
+        println!("Waiting for Meilisearch to finish indexing...");
+        let task = task
+            .wait_for_completion(&meili, None, Some(INDEXING_TIMEOUT))
+            .await?;
+        if task.is_failure() {
+            anyhow::bail!("Meilisearch failed to index the last batch: {}", task.unwrap_failure());
+        }
+    }
+    if !removed.is_empty() {
+        sqlx::query("DELETE FROM removed_papers WHERE paper_id = ANY($1)")
+            .bind(&removed)
+            .execute(&pool)
+            .await?;
+        println!("Removed {} papers no longer in DBLP", removed.len());
+    }
+    match cache::bump_generation(&config.redis_url).await {
+        Ok(generation) => println!("Search cache generation is now {}", generation),
+        Err(e) => eprintln!(
+            "Warning: could not bump the search cache generation ({:#}); cached results stay until they expire",
+            e
+        ),
+    }
+
     println!("All data with links has been successfully synced to Meilisearch.");
     Ok(())
 }
 
-async fn setup_meili_settings(client: &Client, index: &Index) -> anyhow::Result<()> {
+/// Attach a title+abstract embedding to each document under `_vectors`.
+async fn embed_docs(embedder: &Embedder, docs: &mut [PaperDoc]) -> anyhow::Result<()> {
+    for chunk in docs.chunks_mut(EMBED_CHUNK) {
+        let texts: Vec<String> = chunk
+            .iter()
+            .map(|d| match d.abstract_text {
+                Some(ref abs) => format!("{}. {}", d.title, abs),
+                None => d.title.clone(),
+            })
+            .collect();
+        let vectors = embedder.embed(&texts).await?;
+        for (doc, vector) in chunk.iter_mut().zip(vectors) {
+            doc.vectors = Some(HashMap::from([(EMBEDDER_NAME.to_string(), vector)]));
+        }
+    }
+    Ok(())
+}
+
+async fn setup_meili_settings(
+    client: &Client,
+    index: &Index,
+    embedder: Option<&Embedder>,
+) -> anyhow::Result<()> {
     println!("Configuring Meilisearch settings...");
 
-    let task = index.set_filterable_attributes(["venue", "year"]).await?;
+    let task = index.set_filterable_attributes(["venue", "year", "open_access", "page_count", "paper_type"]).await?;
     task.wait_for_completion(client, None, None).await?;
 
     let task = index.set_sortable_attributes(["year", "citation_count"]).await?;
@@ -101,6 +136,49 @@ async fn setup_meili_settings(client: &Client, index: &Index) -> anyhow::Result<
         .await?;
     task.wait_for_completion(client, None, None).await?;
 
+    let synonyms_path = std::env::var("SYNONYMS_FILE").unwrap_or_else(|_| "synonyms.json".into());
+    let synonyms = load_synonyms(&synonyms_path)?;
+    let task = index.set_synonyms(&synonyms).await?;
+    task.wait_for_completion(client, None, None).await?;
+    println!("Pushed {} synonym entries from {}", synonyms.len(), synonyms_path);
+
+    if let Some(embedder) = embedder {
+        let dimensions = embedder.dimensions().await?;
+        let settings = EmbedderSettings {
+            source: EmbedderSource::UserProvided,
+            dimensions: Some(dimensions),
+            ..EmbedderSettings::default()
+        };
+        let task = index
+            .set_embedders(&HashMap::from([(EMBEDDER_NAME.to_string(), settings)]))
+            .await?;
+        task.wait_for_completion(client, None, None).await?;
+        println!("Configured embedder '{}' ({}, {} dims)", EMBEDDER_NAME, embedder.model(), dimensions);
+    }
+
     println!("Meilisearch settings applied.");
     Ok(())
 }
+
+/// The synonym file is a list of groups of interchangeable terms, e.g.
+/// `["rdma", "remote direct memory access"]`. Meilisearch wants a one-way map,
+/// so every term in a group is mapped to all of its siblings.
+fn load_synonyms(path: &str) -> anyhow::Result<HashMap<String, Vec<String>>> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read synonyms file {}", path))?;
+    let groups: Vec<Vec<String>> = serde_json::from_str(&raw)?;
+
+    let mut synonyms: HashMap<String, Vec<String>> = HashMap::new();
+    for group in groups {
+        let terms: Vec<String> = group.iter().map(|t| t.trim().to_lowercase()).collect();
+        for term in &terms {
+            let entry = synonyms.entry(term.clone()).or_default();
+            for other in &terms {
+                if other != term && !entry.contains(other) {
+                    entry.push(other.clone());
+                }
+            }
+        }
+    }
+    Ok(synonyms)
+}